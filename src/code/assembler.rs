@@ -0,0 +1,990 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::code::debug_info::{ChunkDebugInfo, DebugInfo, LineEntry};
+use crate::code::loader::JexBytecode;
+use crate::code::local_names::{ChunkLocalNames, LocalName, LocalNames};
+use crate::code::serializer::serialize_bytecode;
+use crate::exceptions::static_exceptions::AssemblyError;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::operands::{Capture, Operand, OperandKind};
+use crate::instructions::JEX_INSTRUCTIONS;
+use extendable_vm::{Chunk, Code};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// A word or a string literal on a line of assembly
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+}
+
+struct SourceInstruction {
+    line: usize,
+    /// Line of the original program set by `.line`
+    source_line: Option<u32>,
+    op_code: JexOpCode,
+    name: &'static str,
+    args: Vec<Token>,
+    /// Variables captured by `CLOSURE`
+    captures: Vec<Capture>,
+    offset: usize,
+}
+
+struct SourceChunk {
+    /// `None` for the script
+    function: Option<(String, i32)>,
+    constants: Vec<(usize, Token)>,
+    labels: HashMap<String, usize>,
+    instructions: Vec<SourceInstruction>,
+    n_code_bytes: usize,
+    /// Original program file set by `.file`
+    file: Option<String>,
+    source_line: Option<u32>,
+    /// Variables set by `.local`, the variables that are not closed yet end at `usize::MAX`
+    locals: Vec<LocalName>,
+}
+
+/// Compiles a text assembly program into bytecode chunks.
+///
+/// A program is a list of chunks, the first one must be the script:
+///
+/// ```text
+/// .script
+///     CONSTANT @add       ; function constants refer to chunks by name
+///     CONSTANT 2
+///     CONSTANT 10
+///     CALL 2
+///     PRINT
+///
+/// .function add 2         ; name and arity become the first two constants
+/// start:
+///     GET_LOCAL 1
+///     GET_LOCAL 2
+///     ADD
+///     RETURN
+/// ```
+///
+/// Constant arguments are written as literals (`42`, `1.5`, `"text"`, `@function`) and are added
+/// to the constant pool of the chunk, identifier arguments may also be written as bare words.
+/// `#n` refers to the `n`-th constant of the chunk directly and `.const literal` adds a constant explicitly.
+/// Jump arguments are labels or raw offsets, everything after `;` is a comment.
+pub fn assemble(source: &str) -> Result<Code<JexConstant>, AssemblyError> {
+    assemble_bytecode(source).map(|bytecode| bytecode.code)
+}
+
+/// Compiles a text assembly program into bytecode with optional sections.
+///
+/// `.file "name"` and `.line n` set the original program file and line of the following instructions,
+/// if they are used the bytecode gets debug info.
+/// The file is inherited by the following chunks, the line is reset at the start of each chunk.
+///
+/// `.local name slot` names the frame slot starting from the next instruction until the end of the chunk,
+/// `.endlocal name` or another `.local` in the same slot, the names are stored in the local names section.
+pub fn assemble_bytecode(source: &str) -> Result<JexBytecode, AssemblyError> {
+    let source_chunks = parse_chunks(source)?;
+    let chunks = source_chunks.as_slice();
+    let chunk_ids: HashMap<String, usize> = chunks
+        .iter()
+        .enumerate()
+        .filter_map(|(chunk_id, chunk)| {
+            chunk
+                .function
+                .as_ref()
+                .map(|(name, _)| (name.clone(), chunk_id))
+        })
+        .collect();
+    let chunks = chunks
+        .iter()
+        .map(|chunk| compile_chunk(chunk, &chunk_ids))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut bytecode = JexBytecode::new(Code { chunks });
+    let has_debug_info = source_chunks.iter().any(|chunk| {
+        chunk.file.is_some()
+            || chunk
+                .instructions
+                .iter()
+                .any(|instruction| instruction.source_line.is_some())
+    });
+    if has_debug_info {
+        bytecode.debug_info = Some(DebugInfo {
+            chunks: source_chunks.iter().map(chunk_debug_info).collect(),
+        });
+    }
+    if source_chunks.iter().any(|chunk| !chunk.locals.is_empty()) {
+        bytecode.local_names = Some(LocalNames {
+            chunks: source_chunks.iter().map(chunk_local_names).collect(),
+        });
+    }
+    Ok(bytecode)
+}
+
+fn chunk_local_names(chunk: &SourceChunk) -> ChunkLocalNames {
+    let locals = chunk
+        .locals
+        .iter()
+        .map(|local| LocalName {
+            end: local.end.min(chunk.n_code_bytes),
+            ..local.clone()
+        })
+        .collect();
+    ChunkLocalNames { locals }
+}
+
+fn chunk_debug_info(chunk: &SourceChunk) -> ChunkDebugInfo {
+    let mut lines: Vec<LineEntry> = vec![];
+    for instruction in &chunk.instructions {
+        if let Some(line) = instruction.source_line {
+            if lines.last().map(|entry| entry.line) != Some(line) {
+                lines.push(LineEntry {
+                    offset: instruction.offset,
+                    line,
+                });
+            }
+        }
+    }
+    ChunkDebugInfo {
+        file: chunk.file.clone().unwrap_or_default(),
+        lines,
+    }
+}
+
+/// Compiles a text assembly program into the contents of a bytecode file
+pub fn assemble_to_bytes(source: &str) -> Result<Vec<u8>, AssemblyError> {
+    let bytecode = assemble_bytecode(source)?;
+    // the sizes of chunks, constants and strings are checked while assembling
+    Ok(
+        serialize_bytecode(&bytecode)
+            .expect("Assembled code does not fit into the bytecode format"),
+    )
+}
+
+fn parse_chunks(source: &str) -> Result<Vec<SourceChunk>, AssemblyError> {
+    let mut chunks: Vec<SourceChunk> = vec![];
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut tokens = tokenize(line, text)?.into_iter().peekable();
+        // directives
+        if let Some(Token::Word(word)) = tokens.peek() {
+            if word.starts_with('.') {
+                let directive = word.clone();
+                tokens.next();
+                let args: Vec<Token> = tokens.collect();
+                parse_directive(line, &directive, args, &mut chunks)?;
+                continue;
+            }
+        }
+        // labels
+        while let Some(Token::Word(word)) = tokens.peek() {
+            if !word.ends_with(':') {
+                break;
+            }
+            let label = word.trim_end_matches(':').to_string();
+            tokens.next();
+            let chunk = current_chunk(line, &mut chunks)?;
+            if !is_identifier(&label) {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Invalid label name '{}'", label),
+                ));
+            }
+            if chunk
+                .labels
+                .insert(label.clone(), chunk.n_code_bytes)
+                .is_some()
+            {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Label '{}' is already defined in this chunk", label),
+                ));
+            }
+        }
+        // instruction
+        let mnemonic = match tokens.next() {
+            Some(Token::Word(word)) => word,
+            Some(Token::Str(_)) => {
+                return Err(AssemblyError::new(
+                    line,
+                    "Expected an instruction name, found a string".to_string(),
+                ))
+            }
+            None => continue,
+        };
+        let (op_code, name) = find_op_code(&mnemonic).ok_or_else(|| {
+            AssemblyError::new(line, format!("Unknown instruction '{}'", mnemonic))
+        })?;
+        let mut args: Vec<Token> = tokens.collect();
+        let mut captures = vec![];
+        if op_code == JexOpCode::Closure {
+            // `CLOSURE local 1 upvalue 0`, the number of captures is implied
+            captures = parse_captures(line, &args)?;
+            args = vec![Token::Word(captures.len().to_string())];
+        }
+        let operands = op_code.operands();
+        if args.len() != operands.len() {
+            return Err(AssemblyError::new(
+                line,
+                format!(
+                    "{} expects {} argument(s), found {}",
+                    name,
+                    operands.len(),
+                    args.len()
+                ),
+            ));
+        }
+        let chunk = current_chunk(line, &mut chunks)?;
+        let offset = chunk.n_code_bytes;
+        chunk.n_code_bytes += 1
+            + operands
+                .iter()
+                .map(|operand| operand.n_bytes)
+                .sum::<usize>()
+            + captures.len() * Capture::N_BYTES;
+        let source_line = chunk.source_line;
+        chunk.instructions.push(SourceInstruction {
+            line,
+            source_line,
+            op_code,
+            name,
+            args,
+            captures,
+            offset,
+        });
+    }
+    if chunks.is_empty() {
+        return Err(AssemblyError::new(
+            1,
+            "Program must start with a .script chunk".to_string(),
+        ));
+    }
+    Ok(chunks)
+}
+
+fn parse_captures(line: usize, args: &[Token]) -> Result<Vec<Capture>, AssemblyError> {
+    let invalid = || {
+        AssemblyError::new(
+            line,
+            "CLOSURE expects captures like 'local 1' or 'upvalue 0'".to_string(),
+        )
+    };
+    if !args.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    args.chunks(2)
+        .map(|capture| match capture {
+            [Token::Word(kind), Token::Word(index)] => {
+                let index = parse_unsigned(line, index)?;
+                match kind.to_ascii_lowercase().as_str() {
+                    "local" => Ok(Capture::Local(index)),
+                    "upvalue" => Ok(Capture::Upvalue(index)),
+                    _ => Err(invalid()),
+                }
+            }
+            _ => Err(invalid()),
+        })
+        .collect()
+}
+
+fn parse_directive(
+    line: usize,
+    directive: &str,
+    args: Vec<Token>,
+    chunks: &mut Vec<SourceChunk>,
+) -> Result<(), AssemblyError> {
+    match (directive, args.as_slice()) {
+        (".script", []) => {
+            if !chunks.is_empty() {
+                return Err(AssemblyError::new(
+                    line,
+                    ".script must be the first chunk".to_string(),
+                ));
+            }
+            chunks.push(SourceChunk::new(None, None));
+        }
+        (".function", [Token::Word(name), Token::Word(arity)]) => {
+            if chunks.is_empty() {
+                return Err(AssemblyError::new(
+                    line,
+                    "Program must start with a .script chunk".to_string(),
+                ));
+            }
+            if !is_identifier(name) {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Invalid function name '{}'", name),
+                ));
+            }
+            let arity: i32 = arity
+                .parse()
+                .ok()
+                .filter(|arity| *arity >= 0)
+                .ok_or_else(|| AssemblyError::new(line, format!("Invalid arity '{}'", arity)))?;
+            let is_defined = chunks.iter().any(
+                |chunk| matches!(&chunk.function, Some((chunk_name, _)) if chunk_name == name),
+            );
+            if is_defined {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Function '{}' is already defined", name),
+                ));
+            }
+            if chunks.len() > usize::from(u8::MAX) {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Program can have at most {} functions", u8::MAX),
+                ));
+            }
+            string_constant(line, name)?;
+            let file = chunks.last().and_then(|chunk| chunk.file.clone());
+            chunks.push(SourceChunk::new(Some((name.clone(), arity)), file));
+        }
+        (".const", [literal]) => {
+            let chunk = current_chunk(line, chunks)?;
+            chunk.constants.push((line, literal.clone()));
+        }
+        (".file", [Token::Str(file)]) => {
+            let chunk = current_chunk(line, chunks)?;
+            if file.len() > usize::from(u16::MAX) {
+                return Err(AssemblyError::new(
+                    line,
+                    "File name is too long".to_string(),
+                ));
+            }
+            chunk.file = Some(file.clone());
+        }
+        (".line", [Token::Word(source_line)]) => {
+            let source_line = source_line
+                .parse()
+                .map_err(|_| AssemblyError::new(line, format!("Invalid line '{}'", source_line)))?;
+            let chunk = current_chunk(line, chunks)?;
+            chunk.source_line = Some(source_line);
+        }
+        (".local", [Token::Word(name), Token::Word(slot)]) => {
+            let slot = parse_unsigned(line, slot)?;
+            let chunk = current_chunk(line, chunks)?;
+            if !is_identifier(name) || slot > usize::from(u16::MAX) {
+                return Err(AssemblyError::new(
+                    line,
+                    format!("Invalid local '{}' in slot {}", name, slot),
+                ));
+            }
+            let offset = chunk.n_code_bytes;
+            // a new variable in the same slot ends the previous one
+            for local in chunk.open_locals() {
+                if local.slot == slot || local.name == *name {
+                    local.end = offset;
+                }
+            }
+            chunk.locals.push(LocalName {
+                name: name.clone(),
+                slot,
+                start: offset,
+                end: usize::MAX,
+            });
+        }
+        (".endlocal", [Token::Word(name)]) => {
+            let chunk = current_chunk(line, chunks)?;
+            let offset = chunk.n_code_bytes;
+            let local = chunk
+                .open_locals()
+                .find(|local| local.name == *name)
+                .ok_or_else(|| AssemblyError::new(line, format!("Unknown local '{}'", name)))?;
+            local.end = offset;
+        }
+        (".script", _)
+        | (".function", _)
+        | (".const", _)
+        | (".file", _)
+        | (".line", _)
+        | (".local", _)
+        | (".endlocal", _) => {
+            let usage = match directive {
+                ".script" => ".script",
+                ".function" => ".function name arity",
+                ".const" => ".const literal",
+                ".file" => ".file \"name\"",
+                ".line" => ".line number",
+                ".local" => ".local name slot",
+                _ => ".endlocal name",
+            };
+            return Err(AssemblyError::new(
+                line,
+                format!("Invalid directive, expected '{}'", usage),
+            ));
+        }
+        _ => {
+            return Err(AssemblyError::new(
+                line,
+                format!("Unknown directive '{}'", directive),
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn current_chunk(
+    line: usize,
+    chunks: &mut [SourceChunk],
+) -> Result<&mut SourceChunk, AssemblyError> {
+    chunks.last_mut().ok_or_else(|| {
+        AssemblyError::new(line, "Program must start with a .script chunk".to_string())
+    })
+}
+
+fn compile_chunk(
+    chunk: &SourceChunk,
+    chunk_ids: &HashMap<String, usize>,
+) -> Result<Chunk<JexConstant>, AssemblyError> {
+    let mut constants = vec![];
+    if let Some((name, arity)) = &chunk.function {
+        constants.push(JexConstant::String(name.clone()));
+        constants.push(JexConstant::Int(*arity));
+    }
+    for (line, token) in &chunk.constants {
+        let constant = parse_literal(*line, token, chunk_ids)?;
+        constants.push(constant);
+    }
+    let mut code = vec![];
+    for instruction in &chunk.instructions {
+        code.push(instruction.op_code as u8);
+        let next_offset = instruction.offset
+            + 1
+            + instruction
+                .op_code
+                .operands()
+                .iter()
+                .map(|operand| operand.n_bytes)
+                .sum::<usize>()
+            + instruction.captures.len() * Capture::N_BYTES;
+        for (operand, arg) in instruction.op_code.operands().iter().zip(&instruction.args) {
+            let value = resolve_operand(
+                instruction,
+                operand,
+                arg,
+                next_offset,
+                chunk,
+                chunk_ids,
+                &mut constants,
+            )?;
+            let bytes = operand.write(value).ok_or_else(|| {
+                AssemblyError::new(
+                    instruction.line,
+                    format!(
+                        "Argument {} of {} does not fit into {} byte(s)",
+                        value, instruction.name, operand.n_bytes
+                    ),
+                )
+            })?;
+            code.extend(bytes);
+        }
+        for capture in &instruction.captures {
+            let bytes = capture.write().ok_or_else(|| {
+                AssemblyError::new(
+                    instruction.line,
+                    format!(
+                        "Capture {:?} of {} does not fit into 1 byte",
+                        capture, instruction.name
+                    ),
+                )
+            })?;
+            code.extend(bytes);
+        }
+    }
+    let first_line = chunk
+        .instructions
+        .first()
+        .map(|instruction| instruction.line)
+        .unwrap_or(1);
+    if constants.len() > usize::from(u16::MAX) {
+        return Err(AssemblyError::new(
+            first_line,
+            format!(
+                "Chunk has {} constants, at most {} are allowed",
+                constants.len(),
+                u16::MAX
+            ),
+        ));
+    }
+    if code.len() > usize::from(u16::MAX) {
+        return Err(AssemblyError::new(
+            first_line,
+            format!(
+                "Chunk has {} bytes of code, at most {} are allowed",
+                code.len(),
+                u16::MAX
+            ),
+        ));
+    }
+    Ok(Chunk { constants, code })
+}
+
+fn resolve_operand(
+    instruction: &SourceInstruction,
+    operand: &Operand,
+    arg: &Token,
+    next_offset: usize,
+    chunk: &SourceChunk,
+    chunk_ids: &HashMap<String, usize>,
+    constants: &mut Vec<JexConstant>,
+) -> Result<usize, AssemblyError> {
+    let line = instruction.line;
+    if let Token::Word(word) = arg {
+        if let Some(index) = word.strip_prefix('#') {
+            return parse_unsigned(line, index);
+        }
+    }
+    match operand.kind {
+        OperandKind::Constant => {
+            let constant = parse_literal(line, arg, chunk_ids)?;
+            Ok(add_constant(constants, constant))
+        }
+        OperandKind::Identifier => {
+            let name = match arg {
+                Token::Str(string) => string,
+                Token::Word(word) if is_identifier(word) => word,
+                Token::Word(word) => {
+                    return Err(AssemblyError::new(
+                        line,
+                        format!("Expected an identifier, found '{}'", word),
+                    ))
+                }
+            };
+            Ok(add_constant(constants, string_constant(line, name)?))
+        }
+        OperandKind::LocalSlot
+        | OperandKind::Arity
+        | OperandKind::Count
+        | OperandKind::Captures
+        | OperandKind::Upvalue => match arg {
+            Token::Word(word) => parse_unsigned(line, word),
+            Token::Str(_) => Err(AssemblyError::new(
+                line,
+                format!("{} expects a number, found a string", instruction.name),
+            )),
+        },
+        OperandKind::ForwardOffset | OperandKind::BackwardOffset => {
+            let word = match arg {
+                Token::Word(word) => word,
+                Token::Str(_) => {
+                    return Err(AssemblyError::new(
+                        line,
+                        format!("{} expects a label, found a string", instruction.name),
+                    ))
+                }
+            };
+            if word.chars().all(|c| c.is_ascii_digit()) {
+                return parse_unsigned(line, word);
+            }
+            let target = *chunk
+                .labels
+                .get(word)
+                .ok_or_else(|| AssemblyError::new(line, format!("Unknown label '{}'", word)))?;
+            let offset = if operand.kind == OperandKind::ForwardOffset {
+                target.checked_sub(next_offset)
+            } else {
+                next_offset.checked_sub(target)
+            };
+            offset.ok_or_else(|| {
+                let direction = if operand.kind == OperandKind::ForwardOffset {
+                    "before"
+                } else {
+                    "after"
+                };
+                AssemblyError::new(
+                    line,
+                    format!(
+                        "Label '{}' is {} {}, it cannot be reached",
+                        word, direction, instruction.name
+                    ),
+                )
+            })
+        }
+    }
+}
+
+/// Returns the index of `constant` in `constants` adding it if it is not there yet
+fn add_constant(constants: &mut Vec<JexConstant>, constant: JexConstant) -> usize {
+    match constants.iter().position(|existing| *existing == constant) {
+        Some(index) => index,
+        None => {
+            constants.push(constant);
+            constants.len() - 1
+        }
+    }
+}
+
+fn parse_literal(
+    line: usize,
+    token: &Token,
+    chunk_ids: &HashMap<String, usize>,
+) -> Result<JexConstant, AssemblyError> {
+    let word = match token {
+        Token::Str(string) => return string_constant(line, string),
+        Token::Word(word) => word,
+    };
+    if let Some(name) = word.strip_prefix('@') {
+        return chunk_ids
+            .get(name)
+            .map(|chunk_id| JexConstant::Function {
+                chunk_id: *chunk_id,
+            })
+            .ok_or_else(|| AssemblyError::new(line, format!("Unknown function '{}'", name)));
+    }
+    let is_float = word.contains(['.', 'e', 'E', 'i', 'n']);
+    if is_float {
+        word.parse().map(JexConstant::Float).ok()
+    } else {
+        word.parse().map(JexConstant::Int).ok()
+    }
+    .ok_or_else(|| AssemblyError::new(line, format!("Invalid constant '{}'", word)))
+}
+
+fn string_constant(line: usize, string: &str) -> Result<JexConstant, AssemblyError> {
+    if string.len() > usize::from(u16::MAX) {
+        return Err(AssemblyError::new(
+            line,
+            format!(
+                "String is {} bytes long, at most {} are allowed",
+                string.len(),
+                u16::MAX
+            ),
+        ));
+    }
+    Ok(JexConstant::String(string.to_string()))
+}
+
+fn parse_unsigned(line: usize, word: &str) -> Result<usize, AssemblyError> {
+    word.parse()
+        .map_err(|_| AssemblyError::new(line, format!("Expected a number, found '{}'", word)))
+}
+
+fn is_identifier(word: &str) -> bool {
+    let mut chars = word.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn find_op_code(mnemonic: &str) -> Option<(JexOpCode, &'static str)> {
+    let instruction = JEX_INSTRUCTIONS
+        .iter()
+        .find(|instruction| instruction.name.eq_ignore_ascii_case(mnemonic))?;
+    let op_code = JexOpCode::try_from(instruction.op_code).ok()?;
+    Some((op_code, instruction.name))
+}
+
+fn tokenize(line: usize, text: &str) -> Result<Vec<Token>, AssemblyError> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c == ';' {
+            break;
+        } else if c.is_whitespace() || c == ',' {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => {
+                        let escaped = match chars.next() {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('r') => '\r',
+                            Some('0') => '\0',
+                            Some('\\') => '\\',
+                            Some('"') => '"',
+                            other => {
+                                return Err(AssemblyError::new(
+                                    line,
+                                    format!("Invalid escape sequence '\\{}'", other.unwrap_or(' ')),
+                                ))
+                            }
+                        };
+                        string.push(escaped);
+                    }
+                    Some(c) => string.push(c),
+                    None => {
+                        return Err(AssemblyError::new(line, "Unterminated string".to_string()))
+                    }
+                }
+            }
+            tokens.push(Token::Str(string));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || matches!(c, ',' | ';' | '"') {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+impl SourceChunk {
+    fn open_locals(&mut self) -> impl Iterator<Item = &mut LocalName> {
+        self.locals
+            .iter_mut()
+            .filter(|local| local.end == usize::MAX)
+    }
+
+    fn new(function: Option<(String, i32)>, file: Option<String>) -> SourceChunk {
+        SourceChunk {
+            function,
+            constants: vec![],
+            labels: HashMap::new(),
+            instructions: vec![],
+            n_code_bytes: 0,
+            file,
+            source_line: None,
+            locals: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::assembler::{assemble, assemble_bytecode, assemble_to_bytes};
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::code::debug_info::{ChunkDebugInfo, DebugInfo, LineEntry};
+    use crate::code::loader::load_bytecode;
+    use crate::code::local_names::{ChunkLocalNames, LocalName, LocalNames};
+    use crate::instructions::op_codes::JexOpCode;
+
+    #[test]
+    fn it_should_assemble_the_script_with_inline_constants() {
+        let code = assemble(
+            "; 2 * 10\n.script\n    CONSTANT 2\n    CONSTANT 10 ; ten\n    MULTIPLY\n    PRINT\n",
+        )
+        .unwrap();
+        assert_eq!(1, code.chunks.len());
+        assert_eq!(
+            vec![JexConstant::Int(2), JexConstant::Int(10)],
+            code.chunks[0].constants
+        );
+        assert_eq!(
+            vec![
+                JexOpCode::Constant as u8,
+                0,
+                JexOpCode::Constant as u8,
+                1,
+                JexOpCode::Multiply as u8,
+                JexOpCode::Print as u8,
+            ],
+            code.chunks[0].code
+        );
+    }
+
+    #[test]
+    fn it_should_produce_the_same_bytes_as_the_example() {
+        let bytes =
+            assemble_to_bytes(".script\n CONSTANT 2\n CONSTANT 10\n MULTIPLY\n PRINT").unwrap();
+        let example = include_bytes!("../../examples/2_times_10_with_header.bytecode");
+        assert_eq!(example.to_vec(), bytes);
+    }
+
+    #[test]
+    fn it_should_deduplicate_constants_and_resolve_functions() {
+        let code = assemble(
+            r#"
+.script
+    CONSTANT @f
+    CONSTANT "x"
+    DEFINE_GLOBAL x
+    CONSTANT 1.5
+    CALL 1
+.function f 1
+    .const 7
+    CONSTANT #2
+    RETURN
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                JexConstant::Function { chunk_id: 1 },
+                JexConstant::from_str("x"),
+                JexConstant::Float(1.5)
+            ],
+            code.chunks[0].constants
+        );
+        assert_eq!(
+            vec![
+                JexOpCode::Constant as u8,
+                0,
+                JexOpCode::Constant as u8,
+                1,
+                JexOpCode::DefineGlobal as u8,
+                1,
+                JexOpCode::Constant as u8,
+                2,
+                JexOpCode::Call as u8,
+                1
+            ],
+            code.chunks[0].code
+        );
+        assert_eq!(
+            vec![
+                JexConstant::from_str("f"),
+                JexConstant::Int(1),
+                JexConstant::Int(7)
+            ],
+            code.chunks[1].constants
+        );
+        assert_eq!(
+            vec![JexOpCode::Constant as u8, 2, JexOpCode::Return as u8],
+            code.chunks[1].code
+        );
+    }
+
+    #[test]
+    fn it_should_resolve_labels() {
+        let code = assemble(
+            r#"
+.script
+loop: TRUE
+    JUMP_FORWARD_IF_FALSE end
+    JUMP_BACKWARD loop
+end:
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                JexOpCode::True as u8,
+                JexOpCode::JumpForwardIfFalse as u8,
+                2,
+                JexOpCode::JumpBackward as u8,
+                5
+            ],
+            code.chunks[0].code
+        );
+    }
+
+    #[test]
+    fn it_should_produce_debug_info_from_file_and_line_directives() {
+        let bytecode = assemble_bytecode(
+            r#"
+.script
+.file "main.jex"
+.line 1
+    NULL
+    POP
+.line 2
+    TRUE
+.function f 0
+.line 7
+    NULL
+    RETURN
+"#,
+        )
+        .unwrap();
+        let expected = DebugInfo {
+            chunks: vec![
+                ChunkDebugInfo {
+                    file: "main.jex".to_string(),
+                    lines: vec![
+                        LineEntry { offset: 0, line: 1 },
+                        LineEntry { offset: 2, line: 2 },
+                    ],
+                },
+                ChunkDebugInfo {
+                    file: "main.jex".to_string(),
+                    lines: vec![LineEntry { offset: 0, line: 7 }],
+                },
+            ],
+        };
+        assert_eq!(Some(expected), bytecode.debug_info);
+        assert!(assemble_bytecode(".script\nNULL")
+            .unwrap()
+            .debug_info
+            .is_none());
+    }
+
+    #[test]
+    fn it_should_produce_local_names_from_local_directives() {
+        let bytecode = assemble_bytecode(
+            r#"
+.script
+.function f 1
+.local a 1
+    CONSTANT 3
+.local b 2
+    GET_LOCAL 1
+.endlocal a
+.local c 2
+    RETURN
+"#,
+        )
+        .unwrap();
+        let local = |name: &str, slot, start, end| LocalName {
+            name: name.to_string(),
+            slot,
+            start,
+            end,
+        };
+        let expected = LocalNames {
+            chunks: vec![
+                ChunkLocalNames::default(),
+                ChunkLocalNames {
+                    locals: vec![
+                        local("a", 1, 0, 4),
+                        local("b", 2, 2, 4),
+                        local("c", 2, 4, 5),
+                    ],
+                },
+            ],
+        };
+        assert_eq!(Some(expected), bytecode.local_names);
+        assert_eq!(
+            "Line 2: Unknown local 'a'",
+            assemble(".script\n.endlocal a").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn it_should_load_assembled_bytes() {
+        let source = ".script\n CONSTANT \"a \\\"quoted\\\" string\"\n PRINT\n";
+        let bytecode = load_bytecode(&assemble_to_bytes(source).unwrap()).unwrap();
+        let code = assemble(source).unwrap();
+        assert_eq!(code.chunks[0].constants, bytecode.code.chunks[0].constants);
+        assert_eq!(code.chunks[0].code, bytecode.code.chunks[0].code);
+    }
+
+    #[test]
+    fn it_should_report_errors_with_line_numbers() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+        assert_eq!(
+            "Line 1: Program must start with a .script chunk",
+            error("NULL")
+        );
+        assert_eq!("Line 2: Unknown instruction 'NOPE'", error(".script\nNOPE"));
+        assert_eq!(
+            "Line 2: CONSTANT expects 1 argument(s), found 0",
+            error(".script\nCONSTANT")
+        );
+        assert_eq!(
+            "Line 2: Unknown label 'end'",
+            error(".script\nJUMP_FORWARD end")
+        );
+        assert_eq!(
+            "Line 3: Label 'start' is before JUMP_FORWARD, it cannot be reached",
+            error(".script\nstart:\nJUMP_FORWARD start")
+        );
+        assert_eq!(
+            "Line 2: Unknown function 'g'",
+            error(".script\nCONSTANT @g")
+        );
+        assert_eq!(
+            "Line 2: Argument 300 of GET_LOCAL does not fit into 1 byte(s)",
+            error(".script\nGET_LOCAL 300")
+        );
+        assert_eq!(
+            "Line 2: String is 65536 bytes long, at most 65535 are allowed",
+            error(&format!(".script\nCONSTANT \"{}\"", "a".repeat(65536)))
+        );
+    }
+}
@@ -4,23 +4,26 @@ use crate::values::values::{JexFunction, JexValue};
 use extendable_vm::Exception;
 use std::fmt::Debug;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum JexConstant {
     Int(i32),
     String(String),
     Function { chunk_id: usize },
+    Float(f64),
 }
 
 pub enum JexConstantType {
     Int = 0,
     String = 1,
     Function = 2,
+    Float = 3,
 }
 
 impl JexConstant {
     pub fn to_value(&self, machine: &JexMachine) -> Result<JexValue, Exception> {
         let value = match self {
             JexConstant::Int(i) => JexValue::Int(*i),
+            JexConstant::Float(f) => JexValue::Float(*f),
             JexConstant::String(str) => JexValue::from_string(str.clone()),
             JexConstant::Function { chunk_id } => {
                 let func = JexFunction::from_code(machine, *chunk_id)?;
@@ -29,6 +32,7 @@ impl JexConstant {
         };
         Ok(value)
     }
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(str: &str) -> JexConstant {
         JexConstant::String(str.to_string())
     }
@@ -0,0 +1,138 @@
+use crate::exceptions::static_exceptions::{
+    TruncatedBytecodeHeader, UnknownBytecodeFlags, UnsupportedBytecodeVersion,
+};
+use extendable_vm::Exception;
+
+/// Bytes that every bytecode file with a header starts with
+pub const BYTECODE_MAGIC: [u8; 4] = [0x7F, b'J', b'E', b'X'];
+
+/// The newest version of the bytecode format that the VM can run
+pub const CURRENT_BYTECODE_VERSION: u8 = 2;
+
+/// The first version where the number of constants of a chunk is a `u16`, it is a `u8` in older files
+pub const WIDE_CONSTANT_COUNT_VERSION: u8 = 2;
+
+/// Flag of the section that maps code offsets to source files and lines
+pub const DEBUG_INFO_SECTION: u8 = 0b0000_0001;
+
+/// Flag of the section that maps frame slots to names of local variables
+pub const LOCAL_NAMES_SECTION: u8 = 0b0000_0010;
+
+/// Flags of all optional sections that the VM knows about
+pub const KNOWN_SECTION_FLAGS: u8 = DEBUG_INFO_SECTION | LOCAL_NAMES_SECTION;
+
+const HEADER_LENGTH: usize = BYTECODE_MAGIC.len() + 2;
+
+/// Describes the format of a bytecode file.
+///
+/// `version` is the version of the bytecode format and every bit of `flags` marks an optional section
+/// that is included in the file.
+/// Files without a header (produced by older compilers) are described by `BytecodeHeader::legacy()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytecodeHeader {
+    pub version: u8,
+    pub flags: u8,
+}
+
+impl BytecodeHeader {
+    pub fn new(flags: u8) -> BytecodeHeader {
+        BytecodeHeader {
+            version: CURRENT_BYTECODE_VERSION,
+            flags,
+        }
+    }
+
+    pub fn legacy() -> BytecodeHeader {
+        BytecodeHeader {
+            version: 0,
+            flags: 0,
+        }
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.version == 0
+    }
+
+    pub fn has_wide_constant_count(&self) -> bool {
+        self.version >= WIDE_CONSTANT_COUNT_VERSION
+    }
+
+    pub fn has_section(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Reads the header from the beginning of `bytes`.
+    ///
+    /// Returns the header and the number of bytes it occupies.
+    /// If `bytes` do not start with `BYTECODE_MAGIC` the file is considered to be a legacy file without a header.
+    pub fn parse(bytes: &[u8]) -> Result<(BytecodeHeader, usize), Exception> {
+        if !bytes.starts_with(&BYTECODE_MAGIC) {
+            return Ok((BytecodeHeader::legacy(), 0));
+        }
+        if bytes.len() < HEADER_LENGTH {
+            return Err(Exception::from(TruncatedBytecodeHeader));
+        }
+        let version = bytes[BYTECODE_MAGIC.len()];
+        let flags = bytes[BYTECODE_MAGIC.len() + 1];
+        if version == 0 || version > CURRENT_BYTECODE_VERSION {
+            return Err(Exception::from(UnsupportedBytecodeVersion(version)));
+        }
+        if flags & !KNOWN_SECTION_FLAGS != 0 {
+            return Err(Exception::from(UnknownBytecodeFlags(
+                flags & !KNOWN_SECTION_FLAGS,
+            )));
+        }
+        Ok((BytecodeHeader { version, flags }, HEADER_LENGTH))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.is_legacy() {
+            return vec![];
+        }
+        let mut bytes = BYTECODE_MAGIC.to_vec();
+        bytes.push(self.version);
+        bytes.push(self.flags);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::bytecode_header::{BytecodeHeader, CURRENT_BYTECODE_VERSION};
+
+    #[test]
+    fn file_without_magic_should_be_legacy() {
+        let (header, length) = BytecodeHeader::parse(&[2, 0, 2, 0, 0, 0]).unwrap();
+        assert!(header.is_legacy());
+        assert_eq!(0, length);
+    }
+
+    #[test]
+    fn header_should_be_parsed_after_being_written() {
+        let header = BytecodeHeader::new(0);
+        let bytes = header.to_bytes();
+        let (parsed, length) = BytecodeHeader::parse(&bytes).unwrap();
+        assert_eq!(header, parsed);
+        assert_eq!(bytes.len(), length);
+    }
+
+    #[test]
+    fn truncated_header_should_not_be_parsed() {
+        let mut bytes = BytecodeHeader::new(0).to_bytes();
+        bytes.pop();
+        assert!(BytecodeHeader::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn header_from_newer_version_should_not_be_parsed() {
+        let mut bytes = BytecodeHeader::new(0).to_bytes();
+        bytes[4] = CURRENT_BYTECODE_VERSION + 1;
+        assert!(BytecodeHeader::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn header_with_unknown_flags_should_not_be_parsed() {
+        let bytes = BytecodeHeader::new(0b1000_0000).to_bytes();
+        assert!(BytecodeHeader::parse(&bytes).is_err());
+    }
+}
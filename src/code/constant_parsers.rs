@@ -6,7 +6,7 @@ use extendable_vm::{
 
 pub type JexConstantParser = ConstantParser<JexConstant>;
 
-pub const JEX_CONSTANT_PARSERS: [JexConstantParser; 3] = [
+pub const JEX_CONSTANT_PARSERS: [JexConstantParser; 4] = [
     ConstantParser {
         constant_type: JexConstantType::Int as u8,
         parser_fn: parse_int_constant,
@@ -19,6 +19,10 @@ pub const JEX_CONSTANT_PARSERS: [JexConstantParser; 3] = [
         constant_type: JexConstantType::Function as u8,
         parser_fn: parse_function_constant,
     },
+    ConstantParser {
+        constant_type: JexConstantType::Float as u8,
+        parser_fn: parse_float_constant,
+    },
 ];
 
 fn parse_int_constant(
@@ -57,6 +61,18 @@ fn parse_function_constant(
     Ok(JexConstant::Function { chunk_id })
 }
 
+fn parse_float_constant(
+    bytes: &RawBytes,
+    pointer: &mut RawBytesPointer,
+) -> Result<JexConstant, Exception> {
+    let float_bytes = bytes
+        .read_n(pointer, 8)
+        .ok_or_else(|| CodeEndedAt("f64".to_string()))?;
+    let mut le_bytes = [0u8; 8];
+    le_bytes.copy_from_slice(&float_bytes);
+    Ok(JexConstant::Float(f64::from_le_bytes(le_bytes)))
+}
+
 pub struct StringConstantParsingError;
 
 impl From<StringConstantParsingError> for Exception {
@@ -0,0 +1,178 @@
+use crate::code::sections::{write_string, write_u16, SectionReader};
+use crate::exceptions::static_exceptions::{BytecodeSerializationError, InvalidBytecodeSection};
+use std::convert::TryFrom;
+
+const SECTION_NAME: &str = "debug info";
+
+/// Maps the code of chunks to the source files and lines they were compiled from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DebugInfo {
+    /// Debug info of the chunk with the same id, chunks after the end of the vector have no debug info
+    pub chunks: Vec<ChunkDebugInfo>,
+}
+
+/// `lines` are sorted by offset, every entry covers the code from its offset up to the next entry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkDebugInfo {
+    pub file: String,
+    pub lines: Vec<LineEntry>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LineEntry {
+    pub offset: usize,
+    pub line: u32,
+}
+
+impl DebugInfo {
+    pub fn get_chunk(&self, chunk_id: usize) -> Option<&ChunkDebugInfo> {
+        self.chunks.get(chunk_id)
+    }
+
+    /// Returns the source line of the instruction that contains the byte at `offset`
+    pub fn line_at(&self, chunk_id: usize, offset: usize) -> Option<u32> {
+        self.get_chunk(chunk_id)?.line_at(offset)
+    }
+
+    /// Returns `file:line` of the instruction that contains the byte at `offset`.
+    ///
+    /// If the line is not known only the file is returned.
+    pub fn location_at(&self, chunk_id: usize, offset: usize) -> Option<String> {
+        let chunk = self.get_chunk(chunk_id)?;
+        match (chunk.file.is_empty(), chunk.line_at(offset)) {
+            (true, None) => None,
+            (true, Some(line)) => Some(format!("line {}", line)),
+            (false, None) => Some(chunk.file.clone()),
+            (false, Some(line)) => Some(format!("{}:{}", chunk.file, line)),
+        }
+    }
+
+    /// Parses the data of the debug info section.
+    ///
+    /// ```text
+    /// struct DebugInfoSection {
+    ///     n_chunks: u16,
+    ///     chunks: [ChunkDebugInfo] // of `n_chunks` size
+    /// }
+    ///
+    /// struct ChunkDebugInfo {
+    ///     file_length: u16,
+    ///     file: [u8], // utf8
+    ///     n_lines: u16,
+    ///     lines: [LineEntry] // of `n_lines` size, sorted by offset
+    /// }
+    ///
+    /// struct LineEntry {
+    ///     offset: u16,
+    ///     line: u32
+    /// }
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<DebugInfo, InvalidBytecodeSection> {
+        let mut reader = SectionReader::new(SECTION_NAME, bytes);
+        let n_chunks = reader.read_u16("number of chunks")?;
+        let mut chunks = vec![];
+        for _ in 0..n_chunks {
+            let file = reader.read_string("file name")?;
+            let n_lines = reader.read_u16("number of lines")?;
+            let mut lines: Vec<LineEntry> = vec![];
+            for _ in 0..n_lines {
+                let offset = usize::from(reader.read_u16("code offset")?);
+                let line = reader.read_u32("line")?;
+                if lines.last().is_some_and(|last| last.offset >= offset) {
+                    return Err(reader.error(format!(
+                        "Line entries are not sorted by offset in chunk #{}",
+                        chunks.len()
+                    )));
+                }
+                lines.push(LineEntry { offset, line });
+            }
+            chunks.push(ChunkDebugInfo { file, lines });
+        }
+        reader.finish()?;
+        Ok(DebugInfo { chunks })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BytecodeSerializationError> {
+        let mut bytes = vec![];
+        let n_chunks = u16::try_from(self.chunks.len()).map_err(|_| {
+            BytecodeSerializationError::new(
+                self.chunks.len() - 1,
+                "Debug info has too many chunks".to_string(),
+            )
+        })?;
+        bytes.extend_from_slice(&n_chunks.to_le_bytes());
+        for (chunk_id, chunk) in self.chunks.iter().enumerate() {
+            write_string(chunk_id, &chunk.file, "file name", &mut bytes)?;
+            write_u16(chunk_id, chunk.lines.len(), "Number of lines", &mut bytes)?;
+            for entry in &chunk.lines {
+                write_u16(chunk_id, entry.offset, "Offset of a line", &mut bytes)?;
+                bytes.extend_from_slice(&entry.line.to_le_bytes());
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+impl ChunkDebugInfo {
+    pub fn line_at(&self, offset: usize) -> Option<u32> {
+        let index = self
+            .lines
+            .partition_point(|entry| entry.offset <= offset)
+            .checked_sub(1)?;
+        Some(self.lines[index].line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::debug_info::{ChunkDebugInfo, DebugInfo, LineEntry};
+
+    fn debug_info() -> DebugInfo {
+        DebugInfo {
+            chunks: vec![
+                ChunkDebugInfo {
+                    file: "main.jex".to_string(),
+                    lines: vec![
+                        LineEntry { offset: 2, line: 1 },
+                        LineEntry { offset: 5, line: 3 },
+                    ],
+                },
+                ChunkDebugInfo::default(),
+            ],
+        }
+    }
+
+    #[test]
+    fn debug_info_should_be_parsed_after_being_written() {
+        let bytes = debug_info().to_bytes().unwrap();
+        assert_eq!(debug_info(), DebugInfo::parse(&bytes).unwrap());
+    }
+
+    #[test]
+    fn lines_should_be_found_by_offset() {
+        let debug_info = debug_info();
+        assert_eq!(None, debug_info.line_at(0, 1));
+        assert_eq!(Some(1), debug_info.line_at(0, 2));
+        assert_eq!(Some(1), debug_info.line_at(0, 4));
+        assert_eq!(Some(3), debug_info.line_at(0, 100));
+        assert_eq!(None, debug_info.line_at(1, 0));
+        assert_eq!(None, debug_info.line_at(2, 0));
+    }
+
+    #[test]
+    fn locations_should_include_file_and_line() {
+        let debug_info = debug_info();
+        assert_eq!(Some("main.jex".to_string()), debug_info.location_at(0, 0));
+        assert_eq!(Some("main.jex:3".to_string()), debug_info.location_at(0, 5));
+        assert_eq!(None, debug_info.location_at(1, 0));
+    }
+
+    #[test]
+    fn truncated_and_unsorted_debug_info_should_not_be_parsed() {
+        let bytes = debug_info().to_bytes().unwrap();
+        assert!(DebugInfo::parse(&bytes[..bytes.len() - 1]).is_err());
+        let mut unsorted = debug_info();
+        unsorted.chunks[0].lines.reverse();
+        assert!(DebugInfo::parse(&unsorted.to_bytes().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::exceptions::static_exceptions::InvalidBytecode;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::operands::{Capture, Operand, OperandKind};
+use crate::instructions::types::JexInstruction;
+use crate::instructions::JEX_INSTRUCTIONS;
+use extendable_vm::Chunk;
+use std::convert::TryFrom;
+
+/// An instruction that was read from the code of a chunk.
+///
+/// `offset` is the index of the op code in the chunk code, `operands` are the decoded arguments
+/// and `captures` are the variables that a `CLOSURE` instruction captures.
+#[derive(Debug, Clone)]
+pub struct DecodedInstruction {
+    pub offset: usize,
+    pub op_code: JexOpCode,
+    pub name: &'static str,
+    pub operands: Vec<(Operand, usize)>,
+    pub captures: Vec<Capture>,
+    pub n_bytes: usize,
+}
+
+impl DecodedInstruction {
+    /// Offset of the instruction that directly follows this one
+    pub fn next_offset(&self) -> usize {
+        self.offset + self.n_bytes
+    }
+
+    /// Offset that the instruction jumps to or `None` if it is not a jump.
+    ///
+    /// Backward jumps that would land before the start of the chunk also return `None`.
+    pub fn jump_target(&self) -> Option<usize> {
+        self.operands
+            .iter()
+            .find_map(|(operand, value)| match operand.kind {
+                OperandKind::ForwardOffset => Some(self.next_offset() + value),
+                OperandKind::BackwardOffset => self.next_offset().checked_sub(*value),
+                _ => None,
+            })
+    }
+
+    pub fn is_jump(&self) -> bool {
+        self.operands.iter().any(|(operand, _)| {
+            matches!(
+                operand.kind,
+                OperandKind::ForwardOffset | OperandKind::BackwardOffset
+            )
+        })
+    }
+}
+
+/// Finds an instruction with the given op code in `JEX_INSTRUCTIONS`
+pub fn find_instruction(op_code: u8) -> Option<&'static JexInstruction> {
+    JEX_INSTRUCTIONS
+        .iter()
+        .find(|instruction| instruction.op_code == op_code)
+        .copied()
+}
+
+/// Splits the code of a chunk into instructions.
+///
+/// Fails if the code contains an unknown op code or if the code ends before all arguments of an instruction are read.
+pub fn decode_chunk(
+    chunk_id: usize,
+    chunk: &Chunk<JexConstant>,
+) -> Result<Vec<DecodedInstruction>, InvalidBytecode> {
+    let mut instructions = vec![];
+    let mut offset = 0;
+    while offset < chunk.code.len() {
+        let instruction = decode_instruction(chunk_id, &chunk.code, offset)?;
+        offset = instruction.next_offset();
+        instructions.push(instruction);
+    }
+    Ok(instructions)
+}
+
+/// Decodes a single instruction that starts at `offset`
+pub fn decode_instruction(
+    chunk_id: usize,
+    code: &[u8],
+    offset: usize,
+) -> Result<DecodedInstruction, InvalidBytecode> {
+    let raw_op_code = code[offset];
+    let unknown_op_code =
+        || InvalidBytecode::at(chunk_id, offset, format!("Unknown op code {}", raw_op_code));
+    let instruction = find_instruction(raw_op_code).ok_or_else(unknown_op_code)?;
+    let op_code = JexOpCode::try_from(raw_op_code).map_err(|_| unknown_op_code())?;
+    let mut n_bytes = 1 + instruction.instruction_fn.byte_arity();
+    let code_ended = || {
+        InvalidBytecode::at(
+            chunk_id,
+            offset,
+            format!("Code ended while reading arguments of {}", instruction.name),
+        )
+    };
+    if offset + n_bytes > code.len() {
+        return Err(code_ended());
+    }
+    let mut operands = vec![];
+    let mut operand_offset = offset + 1;
+    for operand in op_code.operands() {
+        // the length of the code has already been checked
+        let value = operand.read(&code[operand_offset..]).unwrap();
+        operands.push((*operand, value));
+        operand_offset += operand.n_bytes;
+    }
+    let mut captures = vec![];
+    if let Some((_, n_captures)) = operands
+        .iter()
+        .find(|(operand, _)| operand.kind == OperandKind::Captures)
+    {
+        n_bytes += n_captures * Capture::N_BYTES;
+        if offset + n_bytes > code.len() {
+            return Err(code_ended());
+        }
+        for capture_bytes in code[operand_offset..offset + n_bytes].chunks(Capture::N_BYTES) {
+            let capture = Capture::read(capture_bytes).ok_or_else(|| {
+                InvalidBytecode::at(
+                    chunk_id,
+                    offset,
+                    format!("Unknown capture kind {}", capture_bytes[0]),
+                )
+            })?;
+            captures.push(capture);
+        }
+    }
+    Ok(DecodedInstruction {
+        offset,
+        op_code,
+        name: instruction.name,
+        operands,
+        captures,
+        n_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::decoder::decode_chunk;
+    use crate::instructions::op_codes::JexOpCode;
+    use crate::instructions::operands::Capture;
+    use extendable_vm::Chunk;
+
+    fn chunk(code: Vec<u8>) -> Chunk<crate::code::bytecode_constants::JexConstant> {
+        Chunk {
+            constants: vec![],
+            code,
+        }
+    }
+
+    #[test]
+    fn it_should_decode_instructions_with_arguments() {
+        let code = vec![
+            JexOpCode::True as u8,
+            JexOpCode::JumpForwardLong as u8,
+            1,
+            1,
+            JexOpCode::Constant as u8,
+            7,
+        ];
+        let instructions = decode_chunk(0, &chunk(code)).unwrap();
+        assert_eq!(3, instructions.len());
+        assert_eq!("JUMP_FORWARD_LONG", instructions[1].name);
+        assert_eq!(Some(4 + 257), instructions[1].jump_target());
+        assert_eq!(4, instructions[2].offset);
+        assert_eq!(7, instructions[2].operands[0].1);
+    }
+
+    #[test]
+    fn it_should_decode_captures_of_closures() {
+        let code = vec![
+            JexOpCode::Closure as u8,
+            2,
+            1,
+            3,
+            0,
+            0,
+            JexOpCode::Null as u8,
+        ];
+        let instructions = decode_chunk(0, &chunk(code)).unwrap();
+        assert_eq!(2, instructions.len());
+        assert_eq!(
+            vec![Capture::Local(3), Capture::Upvalue(0)],
+            instructions[0].captures
+        );
+        assert_eq!(6, instructions[1].offset);
+    }
+
+    #[test]
+    fn it_should_fail_on_unknown_op_code() {
+        assert!(decode_chunk(0, &chunk(vec![255])).is_err());
+    }
+
+    #[test]
+    fn it_should_fail_on_missing_arguments() {
+        assert!(decode_chunk(0, &chunk(vec![JexOpCode::ConstantLong as u8, 0])).is_err());
+        assert!(decode_chunk(0, &chunk(vec![JexOpCode::Closure as u8, 1, 1])).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::code::decoder::{decode_instruction, DecodedInstruction};
+use crate::code::loader::JexBytecode;
+use crate::code::local_names::{ChunkLocalNames, LocalNames};
+use crate::instructions::operands::{Capture, OperandKind};
+use crate::values::to_output_string::ToOutputString;
+use crate::values::values::JexFunction;
+use extendable_vm::Code;
+use std::fmt::Write;
+
+/// Produces a human-readable listing of all chunks
+pub fn disassemble(code: &Code<JexConstant>) -> String {
+    disassemble_with_names(code, None)
+}
+
+/// Produces a human-readable listing of all chunks that uses the optional sections of the bytecode.
+///
+/// If the bytecode has local names, they are listed for every chunk and printed next to local slots.
+pub fn disassemble_bytecode(bytecode: &JexBytecode) -> String {
+    disassemble_with_names(&bytecode.code, bytecode.local_names.as_ref())
+}
+
+fn disassemble_with_names(code: &Code<JexConstant>, local_names: Option<&LocalNames>) -> String {
+    let mut listing = String::new();
+    for chunk_id in 0..code.chunks.len() {
+        if chunk_id > 0 {
+            listing.push('\n');
+        }
+        let chunk_local_names = local_names.and_then(|local_names| local_names.get_chunk(chunk_id));
+        listing.push_str(&disassemble_chunk_with_names(
+            code,
+            chunk_id,
+            chunk_local_names,
+        ));
+    }
+    listing
+}
+
+/// Produces a human-readable listing of one chunk.
+///
+/// The listing contains the constant pool and every instruction with its offset, name and arguments.
+/// Constants that instructions refer to are printed next to them and jump targets are printed as absolute offsets.
+pub fn disassemble_chunk(code: &Code<JexConstant>, chunk_id: usize) -> String {
+    disassemble_chunk_with_names(code, chunk_id, None)
+}
+
+fn disassemble_chunk_with_names(
+    code: &Code<JexConstant>,
+    chunk_id: usize,
+    local_names: Option<&ChunkLocalNames>,
+) -> String {
+    let mut listing = String::new();
+    let chunk = match code.get_chunk(chunk_id) {
+        Some(chunk) => chunk,
+        None => return format!("== chunk #{} not found ==\n", chunk_id),
+    };
+    writeln!(
+        listing,
+        "== chunk #{} {} ==",
+        chunk_id,
+        chunk_title(code, chunk_id)
+    )
+    .unwrap();
+    writeln!(listing, "constants:").unwrap();
+    for (constant_id, constant) in chunk.constants.iter().enumerate() {
+        writeln!(
+            listing,
+            "  #{:<3} {}",
+            constant_id,
+            describe_constant(code, constant)
+        )
+        .unwrap();
+    }
+    if let Some(local_names) = local_names.filter(|local_names| !local_names.locals.is_empty()) {
+        writeln!(listing, "locals:").unwrap();
+        for local in &local_names.locals {
+            writeln!(
+                listing,
+                "  {:<4} {} ({:04}..{:04})",
+                local.slot, local.name, local.start, local.end
+            )
+            .unwrap();
+        }
+    }
+    writeln!(listing, "code:").unwrap();
+    let mut offset = 0;
+    while offset < chunk.code.len() {
+        match decode_instruction(chunk_id, &chunk.code, offset) {
+            Ok(instruction) => {
+                writeln!(
+                    listing,
+                    "  {}",
+                    describe_instruction(code, chunk_id, &instruction, local_names)
+                )
+                .unwrap();
+                offset = instruction.next_offset();
+            }
+            Err(error) => {
+                writeln!(listing, "  {:04}  <{}>", offset, error.message).unwrap();
+                break;
+            }
+        }
+    }
+    listing
+}
+
+fn chunk_title(code: &Code<JexConstant>, chunk_id: usize) -> String {
+    if chunk_id == 0 {
+        return "<script>".to_string();
+    }
+    JexFunction::from_chunk(code, chunk_id)
+        .map(|function| function.to_output_string())
+        .unwrap_or_else(|_| "<not a function>".to_string())
+}
+
+fn describe_constant(code: &Code<JexConstant>, constant: &JexConstant) -> String {
+    match constant {
+        JexConstant::Int(int) => int.to_output_string(),
+        JexConstant::Float(float) => float.to_output_string(),
+        JexConstant::String(string) => format!("{:?}", string),
+        JexConstant::Function { chunk_id } => {
+            if *chunk_id == 0 {
+                return "<invalid function chunk #0>".to_string();
+            }
+            JexFunction::from_chunk(code, *chunk_id)
+                .map(|function| format!("{} (chunk #{})", function.to_output_string(), chunk_id))
+                .unwrap_or_else(|_| format!("<invalid function chunk #{}>", chunk_id))
+        }
+    }
+}
+
+fn describe_instruction(
+    code: &Code<JexConstant>,
+    chunk_id: usize,
+    instruction: &DecodedInstruction,
+    local_names: Option<&ChunkLocalNames>,
+) -> String {
+    let mut text = format!("{:04}  {}", instruction.offset, instruction.name);
+    let mut comments = vec![];
+    for (operand, value) in &instruction.operands {
+        if operand.kind == OperandKind::Captures {
+            // the number of captures is implied by the list of captures
+            continue;
+        }
+        write!(text, " {}", value).unwrap();
+        match operand.kind {
+            OperandKind::Constant | OperandKind::Identifier => {
+                let comment = code
+                    .get_constant(chunk_id, *value)
+                    .map(|constant| describe_constant(code, constant))
+                    .unwrap_or_else(|_| "<missing constant>".to_string());
+                comments.push(comment);
+            }
+            OperandKind::ForwardOffset | OperandKind::BackwardOffset => {
+                let comment = instruction
+                    .jump_target()
+                    .map(|target| format!("-> {:04}", target))
+                    .unwrap_or_else(|| "-> <before chunk start>".to_string());
+                comments.push(comment);
+            }
+            OperandKind::LocalSlot => {
+                let name = local_names
+                    .and_then(|local_names| local_names.name_at(*value, instruction.offset));
+                if let Some(name) = name {
+                    comments.push(name.to_string());
+                }
+            }
+            OperandKind::Arity
+            | OperandKind::Count
+            | OperandKind::Captures
+            | OperandKind::Upvalue => {}
+        }
+    }
+    for capture in &instruction.captures {
+        match capture {
+            Capture::Local(slot) => {
+                write!(text, " local {}", slot).unwrap();
+                let name = local_names
+                    .and_then(|local_names| local_names.name_at(*slot, instruction.offset));
+                if let Some(name) = name {
+                    comments.push(name.to_string());
+                }
+            }
+            Capture::Upvalue(index) => write!(text, " upvalue {}", index).unwrap(),
+        }
+    }
+    if comments.is_empty() {
+        text
+    } else {
+        format!("{:<32}; {}", text, comments.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::assembler::{assemble, assemble_bytecode};
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::code::disassembler::{disassemble, disassemble_bytecode};
+    use crate::instructions::op_codes::JexOpCode;
+    use extendable_vm::{Chunk, Code};
+
+    #[test]
+    fn it_should_print_instructions_with_constants_and_jump_targets() {
+        let code = Code {
+            chunks: vec![
+                Chunk {
+                    constants: vec![JexConstant::Function { chunk_id: 1 }],
+                    code: vec![
+                        JexOpCode::Constant as u8,
+                        0,
+                        JexOpCode::JumpForward as u8,
+                        1,
+                        JexOpCode::Null as u8,
+                        JexOpCode::Call as u8,
+                        0,
+                    ],
+                },
+                Chunk {
+                    constants: vec![JexConstant::from_str("f"), JexConstant::Int(0)],
+                    code: vec![200],
+                },
+            ],
+        };
+        let expected = "\
+== chunk #0 <script> ==
+constants:
+  #0   function f(0 params) (chunk #1)
+code:
+  0000  CONSTANT 0                ; function f(0 params) (chunk #1)
+  0002  JUMP_FORWARD 1            ; -> 0005
+  0004  NULL
+  0005  CALL 0
+
+== chunk #1 function f(0 params) ==
+constants:
+  #0   \"f\"
+  #1   0
+code:
+  0000  <Unknown op code 200>
+";
+        assert_eq!(expected, disassemble(&code));
+    }
+
+    #[test]
+    fn it_should_print_local_names() {
+        let bytecode =
+            assemble_bytecode(".script\n.local x 1\n  GET_LOCAL 1\n  GET_LOCAL 2").unwrap();
+        let expected = "\
+== chunk #0 <script> ==
+constants:
+locals:
+  1    x (0000..0004)
+code:
+  0000  GET_LOCAL 1               ; x
+  0002  GET_LOCAL 2
+";
+        assert_eq!(expected, disassemble_bytecode(&bytecode));
+    }
+
+    #[test]
+    fn it_should_print_captures_of_closures() {
+        let code = assemble(
+            ".script
+  NULL
+  CLOSURE local 1 upvalue 0
+  POP",
+        )
+        .unwrap();
+        let expected = "\
+== chunk #0 <script> ==
+constants:
+code:
+  0000  NULL
+  0001  CLOSURE local 1 upvalue 0
+  0007  POP
+";
+        assert_eq!(expected, disassemble(&code));
+    }
+}
@@ -0,0 +1,212 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::code::bytecode_header::{BytecodeHeader, DEBUG_INFO_SECTION, LOCAL_NAMES_SECTION};
+use crate::code::constant_parsers::JEX_CONSTANT_PARSERS;
+use crate::code::debug_info::DebugInfo;
+use crate::code::local_names::LocalNames;
+use crate::code::sections::split_section;
+use extendable_vm::parsing_exceptions::{
+    ChunkParsingError, CodeEndedAt, EmptyCode, IllegalConstant, UnknownConstantType,
+};
+use extendable_vm::{
+    ByteReadable, Chunk, Code, CodeParser, ConstantParserTable, Exception, RawBytes,
+    RawBytesPointer,
+};
+
+/// Bytecode that was loaded from a file.
+///
+/// `debug_info` and `local_names` are present if the file contains their sections.
+pub struct JexBytecode {
+    pub header: BytecodeHeader,
+    pub code: Code<JexConstant>,
+    pub debug_info: Option<DebugInfo>,
+    pub local_names: Option<LocalNames>,
+}
+
+impl JexBytecode {
+    /// Bytecode with the current header and without optional sections
+    pub fn new(code: Code<JexConstant>) -> JexBytecode {
+        JexBytecode {
+            header: BytecodeHeader::new(0),
+            code,
+            debug_info: None,
+            local_names: None,
+        }
+    }
+}
+
+/// Parses a bytecode file.
+///
+/// The file may start with a `BytecodeHeader`, otherwise it is parsed as a legacy headerless file.
+pub fn load_bytecode(bytes: &[u8]) -> Result<JexBytecode, Exception> {
+    let (header, header_length) = BytecodeHeader::parse(bytes)?;
+    let mut rest = &bytes[header_length..];
+    let debug_info = if header.has_section(DEBUG_INFO_SECTION) {
+        let section = split_section("debug info", &mut rest)?;
+        Some(DebugInfo::parse(section)?)
+    } else {
+        None
+    };
+    let local_names = if header.has_section(LOCAL_NAMES_SECTION) {
+        let section = split_section("local names", &mut rest)?;
+        Some(LocalNames::parse(section)?)
+    } else {
+        None
+    };
+    let chunk_bytes = RawBytes::from_bytes(rest.to_vec());
+    let const_parser_table = ConstantParserTable::parsers(&JEX_CONSTANT_PARSERS);
+    let code = if header.has_wide_constant_count() {
+        parse_wide_code(&chunk_bytes, &const_parser_table)?
+    } else {
+        CodeParser::new(&const_parser_table).parse(&chunk_bytes)?
+    };
+    Ok(JexBytecode {
+        header,
+        code,
+        debug_info,
+        local_names,
+    })
+}
+
+/// Parses chunks like `CodeParser` except that the number of constants of a chunk is a `u16`
+fn parse_wide_code(
+    bytes: &RawBytes,
+    parsers: &ConstantParserTable<JexConstant>,
+) -> Result<Code<JexConstant>, Exception> {
+    let mut chunks = vec![];
+    let mut ptr = RawBytesPointer::new();
+    while bytes.has_next(&ptr) {
+        let chunk = parse_wide_chunk(bytes, &mut ptr, parsers)
+            .map_err(|err| ChunkParsingError(chunks.len(), err))?;
+        chunks.push(chunk);
+    }
+    if chunks.is_empty() {
+        Err(Exception::from(EmptyCode))
+    } else {
+        Ok(Code { chunks })
+    }
+}
+
+fn parse_wide_chunk(
+    bytes: &RawBytes,
+    ptr: &mut RawBytesPointer,
+    parsers: &ConstantParserTable<JexConstant>,
+) -> Result<Chunk<JexConstant>, Exception> {
+    let n_constants = bytes
+        .read_u16(ptr)
+        .ok_or_else(|| CodeEndedAt("n_constants".to_string()))?;
+    let mut constants = vec![];
+    for _ in 0..n_constants {
+        let constant_type = bytes
+            .read(ptr)
+            .ok_or_else(|| CodeEndedAt("constant_type".to_string()))?;
+        let parser = parsers
+            .get_parser(constant_type)
+            .ok_or(UnknownConstantType(constant_type))?;
+        let constant = (parser.parser_fn)(bytes, ptr)
+            .map_err(|err| IllegalConstant(parser.constant_type, err))?;
+        constants.push(constant);
+    }
+    let n_code_bytes = bytes
+        .read_u16(ptr)
+        .ok_or_else(|| CodeEndedAt("n_code_bytes".to_string()))?;
+    let code = bytes
+        .read_n(ptr, usize::from(n_code_bytes))
+        .ok_or_else(|| CodeEndedAt("code".to_string()))?;
+    Ok(Chunk { constants, code })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::code::bytecode_header::{BytecodeHeader, DEBUG_INFO_SECTION};
+    use crate::code::debug_info::{ChunkDebugInfo, DebugInfo, LineEntry};
+    use crate::code::loader::load_bytecode;
+    use crate::code::sections::write_section;
+
+    const HEADERLESS_CHUNK: [u8; 8] = [1, 0, 20, 0, 0, 0, 0, 0];
+
+    /// The same chunk where the number of constants is a `u16`
+    const WIDE_CHUNK: [u8; 9] = [1, 0, 0, 20, 0, 0, 0, 0, 0];
+
+    fn version_1_header(flags: u8) -> BytecodeHeader {
+        BytecodeHeader { version: 1, flags }
+    }
+
+    #[test]
+    fn headerless_bytecode_should_be_loaded_as_legacy() {
+        let bytecode = load_bytecode(&HEADERLESS_CHUNK).unwrap();
+        assert!(bytecode.header.is_legacy());
+        assert_eq!(
+            vec![JexConstant::Int(20)],
+            bytecode.code.chunks[0].constants
+        );
+    }
+
+    #[test]
+    fn bytecode_with_header_should_be_loaded() {
+        let mut bytes = BytecodeHeader::new(0).to_bytes();
+        bytes.extend_from_slice(&WIDE_CHUNK);
+        let bytecode = load_bytecode(&bytes).unwrap();
+        assert_eq!(BytecodeHeader::new(0), bytecode.header);
+        assert_eq!(
+            vec![JexConstant::Int(20)],
+            bytecode.code.chunks[0].constants
+        );
+    }
+
+    #[test]
+    fn bytecode_of_version_1_should_be_loaded_with_narrow_constant_count() {
+        let mut bytes = version_1_header(0).to_bytes();
+        bytes.extend_from_slice(&HEADERLESS_CHUNK);
+        let bytecode = load_bytecode(&bytes).unwrap();
+        assert_eq!(version_1_header(0), bytecode.header);
+        assert_eq!(
+            vec![JexConstant::Int(20)],
+            bytecode.code.chunks[0].constants
+        );
+    }
+
+    #[test]
+    fn debug_info_section_should_be_loaded() {
+        let debug_info = DebugInfo {
+            chunks: vec![ChunkDebugInfo {
+                file: "main.jex".to_string(),
+                lines: vec![LineEntry { offset: 0, line: 1 }],
+            }],
+        };
+        let mut bytes = BytecodeHeader::new(DEBUG_INFO_SECTION).to_bytes();
+        write_section(&debug_info.to_bytes().unwrap(), &mut bytes);
+        bytes.extend_from_slice(&WIDE_CHUNK);
+        let bytecode = load_bytecode(&bytes).unwrap();
+        assert_eq!(Some(debug_info), bytecode.debug_info);
+        assert_eq!(
+            vec![JexConstant::Int(20)],
+            bytecode.code.chunks[0].constants
+        );
+    }
+
+    #[test]
+    fn truncated_wide_chunk_should_not_be_loaded() {
+        let mut bytes = BytecodeHeader::new(0).to_bytes();
+        bytes.extend_from_slice(&WIDE_CHUNK[..WIDE_CHUNK.len() - 1]);
+        let error = load_bytecode(&bytes).err().unwrap();
+        assert_eq!(
+            "Could not parse chunk #0: [Syntax] CodeEndedAt: Code ended while reading n_code_bytes",
+            error.message
+        );
+    }
+
+    #[test]
+    fn bytecode_with_truncated_section_should_not_be_loaded() {
+        let mut bytes = BytecodeHeader::new(DEBUG_INFO_SECTION).to_bytes();
+        bytes.extend_from_slice(&[100, 0, 0, 0]);
+        bytes.extend_from_slice(&WIDE_CHUNK);
+        assert!(load_bytecode(&bytes).is_err());
+    }
+
+    #[test]
+    fn bytecode_with_only_header_should_not_be_loaded() {
+        let bytes = BytecodeHeader::new(0).to_bytes();
+        assert!(load_bytecode(&bytes).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::code::sections::{write_string, write_u16, SectionReader};
+use crate::exceptions::static_exceptions::{BytecodeSerializationError, InvalidBytecodeSection};
+use std::convert::TryFrom;
+
+const SECTION_NAME: &str = "local names";
+
+/// Names of the local variables of chunks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalNames {
+    /// Local names of the chunk with the same id, chunks after the end of the vector have no names
+    pub chunks: Vec<ChunkLocalNames>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkLocalNames {
+    pub locals: Vec<LocalName>,
+}
+
+/// A local variable `name` that is stored in the frame slot `slot` while the code
+/// from `start` (inclusive) to `end` (exclusive) is executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalName {
+    pub name: String,
+    pub slot: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl LocalName {
+    pub fn is_live_at(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+impl LocalNames {
+    pub fn get_chunk(&self, chunk_id: usize) -> Option<&ChunkLocalNames> {
+        self.chunks.get(chunk_id)
+    }
+
+    /// Returns the name of the variable in `slot` at `offset`
+    pub fn name_at(&self, chunk_id: usize, slot: usize, offset: usize) -> Option<&str> {
+        self.get_chunk(chunk_id)?.name_at(slot, offset)
+    }
+
+    /// Parses the data of the local names section.
+    ///
+    /// ```text
+    /// struct LocalNamesSection {
+    ///     n_chunks: u16,
+    ///     chunks: [ChunkLocalNames] // of `n_chunks` size
+    /// }
+    ///
+    /// struct ChunkLocalNames {
+    ///     n_locals: u16,
+    ///     locals: [LocalName] // of `n_locals` size
+    /// }
+    ///
+    /// struct LocalName {
+    ///     slot: u16,
+    ///     start: u16,
+    ///     end: u16,
+    ///     name_length: u16,
+    ///     name: [u8] // utf8
+    /// }
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<LocalNames, InvalidBytecodeSection> {
+        let mut reader = SectionReader::new(SECTION_NAME, bytes);
+        let n_chunks = reader.read_u16("number of chunks")?;
+        let mut chunks = vec![];
+        for _ in 0..n_chunks {
+            let n_locals = reader.read_u16("number of locals")?;
+            let mut locals = vec![];
+            for _ in 0..n_locals {
+                let slot = usize::from(reader.read_u16("slot")?);
+                let start = usize::from(reader.read_u16("code range start")?);
+                let end = usize::from(reader.read_u16("code range end")?);
+                let name = reader.read_string("local name")?;
+                if start > end {
+                    return Err(reader.error(format!(
+                        "Code range of local '{}' in chunk #{} ends before it starts",
+                        name,
+                        chunks.len()
+                    )));
+                }
+                locals.push(LocalName {
+                    name,
+                    slot,
+                    start,
+                    end,
+                });
+            }
+            chunks.push(ChunkLocalNames { locals });
+        }
+        reader.finish()?;
+        Ok(LocalNames { chunks })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BytecodeSerializationError> {
+        let mut bytes = vec![];
+        let n_chunks = u16::try_from(self.chunks.len()).map_err(|_| {
+            BytecodeSerializationError::new(
+                self.chunks.len() - 1,
+                "Local names have too many chunks".to_string(),
+            )
+        })?;
+        bytes.extend_from_slice(&n_chunks.to_le_bytes());
+        for (chunk_id, chunk) in self.chunks.iter().enumerate() {
+            write_u16(chunk_id, chunk.locals.len(), "Number of locals", &mut bytes)?;
+            for local in &chunk.locals {
+                write_u16(chunk_id, local.slot, "Slot of a local", &mut bytes)?;
+                write_u16(chunk_id, local.start, "Start of a local", &mut bytes)?;
+                write_u16(chunk_id, local.end, "End of a local", &mut bytes)?;
+                write_string(chunk_id, &local.name, "local name", &mut bytes)?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+impl ChunkLocalNames {
+    pub fn name_at(&self, slot: usize, offset: usize) -> Option<&str> {
+        self.locals
+            .iter()
+            .find(|local| local.slot == slot && local.is_live_at(offset))
+            .map(|local| local.name.as_str())
+    }
+
+    /// Returns the variables that are live at `offset` ordered by slot
+    pub fn live_at(&self, offset: usize) -> Vec<&LocalName> {
+        let mut locals: Vec<&LocalName> = self
+            .locals
+            .iter()
+            .filter(|local| local.is_live_at(offset))
+            .collect();
+        locals.sort_by_key(|local| local.slot);
+        locals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::local_names::{ChunkLocalNames, LocalName, LocalNames};
+
+    fn local(name: &str, slot: usize, start: usize, end: usize) -> LocalName {
+        LocalName {
+            name: name.to_string(),
+            slot,
+            start,
+            end,
+        }
+    }
+
+    fn local_names() -> LocalNames {
+        LocalNames {
+            chunks: vec![ChunkLocalNames {
+                locals: vec![
+                    local("name", 2, 4, 10),
+                    local("x", 1, 0, 10),
+                    local("y", 1, 10, 20),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn local_names_should_be_parsed_after_being_written() {
+        let bytes = local_names().to_bytes().unwrap();
+        assert_eq!(local_names(), LocalNames::parse(&bytes).unwrap());
+    }
+
+    #[test]
+    fn names_should_depend_on_code_range() {
+        let local_names = local_names();
+        assert_eq!(Some("x"), local_names.name_at(0, 1, 0));
+        assert_eq!(Some("y"), local_names.name_at(0, 1, 10));
+        assert_eq!(None, local_names.name_at(0, 2, 3));
+        assert_eq!(None, local_names.name_at(0, 1, 20));
+        assert_eq!(None, local_names.name_at(1, 1, 0));
+    }
+
+    #[test]
+    fn live_locals_should_be_ordered_by_slot() {
+        let local_names = local_names();
+        let names: Vec<&str> = local_names.chunks[0]
+            .live_at(5)
+            .iter()
+            .map(|local| local.name.as_str())
+            .collect();
+        assert_eq!(vec!["x", "name"], names);
+    }
+
+    #[test]
+    fn invalid_code_ranges_should_not_be_parsed() {
+        let mut local_names = local_names();
+        local_names.chunks[0].locals[0].end = 0;
+        assert!(LocalNames::parse(&local_names.to_bytes().unwrap()).is_err());
+    }
+}
@@ -1,2 +1,12 @@
+pub mod assembler;
 pub mod bytecode_constants;
+pub mod bytecode_header;
 pub mod constant_parsers;
+pub mod debug_info;
+pub mod decoder;
+pub mod disassembler;
+pub mod loader;
+pub mod local_names;
+pub mod sections;
+pub mod serializer;
+pub mod verifier;
@@ -0,0 +1,124 @@
+use crate::exceptions::static_exceptions::{BytecodeSerializationError, InvalidBytecodeSection};
+use std::convert::TryFrom;
+
+/// Reads little-endian values from the data of an optional section
+pub struct SectionReader<'a> {
+    section: &'static str,
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> SectionReader<'a> {
+    pub fn new(section: &'static str, bytes: &'a [u8]) -> SectionReader<'a> {
+        SectionReader {
+            section,
+            bytes,
+            offset: 0,
+        }
+    }
+
+    pub fn error(&self, message: String) -> InvalidBytecodeSection {
+        InvalidBytecodeSection {
+            section: self.section,
+            message,
+        }
+    }
+
+    pub fn read_n(&mut self, n: usize, what: &str) -> Result<&'a [u8], InvalidBytecodeSection> {
+        if self.offset + n > self.bytes.len() {
+            return Err(self.error(format!(
+                "Section ended at byte {} while reading {}",
+                self.offset, what
+            )));
+        }
+        let bytes = &self.bytes[self.offset..self.offset + n];
+        self.offset += n;
+        Ok(bytes)
+    }
+
+    pub fn read_u16(&mut self, what: &str) -> Result<u16, InvalidBytecodeSection> {
+        let bytes = self.read_n(2, what)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_u32(&mut self, what: &str) -> Result<u32, InvalidBytecodeSection> {
+        let bytes = self.read_n(4, what)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a `u16`-prefixed UTF-8 string
+    pub fn read_string(&mut self, what: &str) -> Result<String, InvalidBytecodeSection> {
+        let length = usize::from(self.read_u16(what)?);
+        let bytes = self.read_n(length, what)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| self.error(format!("Could not parse utf8 string in {}", what)))
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.bytes.len()
+    }
+
+    /// Fails if not all bytes of the section were read
+    pub fn finish(&self) -> Result<(), InvalidBytecodeSection> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error(format!(
+                "Section has {} unexpected bytes at the end",
+                self.bytes.len() - self.offset
+            )))
+        }
+    }
+}
+
+/// Splits the next `u32`-prefixed section off the beginning of `bytes`
+pub fn split_section<'a>(
+    section: &'static str,
+    bytes: &mut &'a [u8],
+) -> Result<&'a [u8], InvalidBytecodeSection> {
+    let mut reader = SectionReader::new(section, bytes);
+    let length = reader.read_u32("section length")? as usize;
+    let data = reader.read_n(length, "section data")?;
+    *bytes = &bytes[4 + length..];
+    Ok(data)
+}
+
+/// Appends `data` to `bytes` as a `u32`-prefixed section
+pub fn write_section(data: &[u8], bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(data);
+}
+
+/// Writes a number that must fit into a `u16`, `what` describes the number in the error
+pub fn write_u16(
+    chunk_id: usize,
+    value: usize,
+    what: &str,
+    bytes: &mut Vec<u8>,
+) -> Result<(), BytecodeSerializationError> {
+    let value = u16::try_from(value).map_err(|_| {
+        BytecodeSerializationError::new(
+            chunk_id,
+            format!("{} is {}, at most {} is allowed", what, value, u16::MAX),
+        )
+    })?;
+    bytes.extend_from_slice(&value.to_le_bytes());
+    Ok(())
+}
+
+/// Writes a `u16`-prefixed UTF-8 string
+pub fn write_string(
+    chunk_id: usize,
+    string: &str,
+    what: &str,
+    bytes: &mut Vec<u8>,
+) -> Result<(), BytecodeSerializationError> {
+    write_u16(
+        chunk_id,
+        string.len(),
+        &format!("Length of {}", what),
+        bytes,
+    )?;
+    bytes.extend_from_slice(string.as_bytes());
+    Ok(())
+}
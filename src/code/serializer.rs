@@ -0,0 +1,318 @@
+use crate::code::bytecode_constants::{JexConstant, JexConstantType};
+use crate::code::bytecode_header::{BytecodeHeader, DEBUG_INFO_SECTION, LOCAL_NAMES_SECTION};
+use crate::code::loader::JexBytecode;
+use crate::code::sections::write_section;
+use crate::exceptions::static_exceptions::BytecodeSerializationError;
+use extendable_vm::{Chunk, Code};
+use std::convert::TryFrom;
+
+/// Encodes a bytecode file: the header, the optional sections and the chunks.
+///
+/// This is the inverse of `load_bytecode`, a legacy header produces a file without a header.
+/// The flags of the header are set according to the sections that are present in `bytecode`.
+pub fn serialize_bytecode(bytecode: &JexBytecode) -> Result<Vec<u8>, BytecodeSerializationError> {
+    let mut flags = 0;
+    let mut sections = vec![];
+    if let Some(debug_info) = &bytecode.debug_info {
+        flags |= DEBUG_INFO_SECTION;
+        write_section(&debug_info.to_bytes()?, &mut sections);
+    }
+    if let Some(local_names) = &bytecode.local_names {
+        flags |= LOCAL_NAMES_SECTION;
+        write_section(&local_names.to_bytes()?, &mut sections);
+    }
+    if bytecode.header.is_legacy() && flags != 0 {
+        return Err(BytecodeSerializationError::new(
+            0,
+            "Legacy bytecode files cannot contain optional sections".to_string(),
+        ));
+    }
+    let header = BytecodeHeader {
+        version: bytecode.header.version,
+        flags,
+    };
+    let mut bytes = header.to_bytes();
+    bytes.extend(sections);
+    bytes.extend(serialize_code(&bytecode.code, &header)?);
+    Ok(bytes)
+}
+
+/// Encodes the chunks in the format that `load_bytecode` reads for files with the header.
+///
+/// Fails if a value does not fit into the format: a chunk with more than `u16::MAX` constants
+/// (255 in legacy files and files of version 1) or more than `u16::MAX` bytes of code,
+/// a string longer than `u16::MAX` bytes or a chunk id above 255.
+pub fn serialize_code(
+    code: &Code<JexConstant>,
+    header: &BytecodeHeader,
+) -> Result<Vec<u8>, BytecodeSerializationError> {
+    let mut bytes = vec![];
+    for (chunk_id, chunk) in code.chunks.iter().enumerate() {
+        serialize_chunk(chunk_id, chunk, header, &mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+fn serialize_chunk(
+    chunk_id: usize,
+    chunk: &Chunk<JexConstant>,
+    header: &BytecodeHeader,
+    bytes: &mut Vec<u8>,
+) -> Result<(), BytecodeSerializationError> {
+    let n_constants = chunk.constants.len();
+    let too_many_constants = |max: usize| {
+        BytecodeSerializationError::new(
+            chunk_id,
+            format!(
+                "Chunk has {} constants, at most {} are allowed",
+                n_constants, max
+            ),
+        )
+    };
+    if header.has_wide_constant_count() {
+        let n_constants =
+            u16::try_from(n_constants).map_err(|_| too_many_constants(usize::from(u16::MAX)))?;
+        bytes.extend_from_slice(&n_constants.to_le_bytes());
+    } else {
+        let n_constants =
+            u8::try_from(n_constants).map_err(|_| too_many_constants(usize::from(u8::MAX)))?;
+        bytes.push(n_constants);
+    }
+    for constant in &chunk.constants {
+        serialize_constant(chunk_id, constant, bytes)?;
+    }
+    let n_code_bytes = u16::try_from(chunk.code.len()).map_err(|_| {
+        BytecodeSerializationError::new(
+            chunk_id,
+            format!(
+                "Chunk has {} bytes of code, at most {} are allowed",
+                chunk.code.len(),
+                u16::MAX
+            ),
+        )
+    })?;
+    bytes.extend_from_slice(&n_code_bytes.to_le_bytes());
+    bytes.extend_from_slice(&chunk.code);
+    Ok(())
+}
+
+/// Encodes a constant as its `JexConstantType` tag followed by its data
+pub fn serialize_constant(
+    chunk_id: usize,
+    constant: &JexConstant,
+    bytes: &mut Vec<u8>,
+) -> Result<(), BytecodeSerializationError> {
+    match constant {
+        JexConstant::Int(int) => {
+            bytes.push(JexConstantType::Int as u8);
+            bytes.extend_from_slice(&int.to_le_bytes());
+        }
+        JexConstant::String(string) => {
+            let length = u16::try_from(string.len()).map_err(|_| {
+                BytecodeSerializationError::new(
+                    chunk_id,
+                    format!(
+                        "String constant is {} bytes long, at most {} are allowed",
+                        string.len(),
+                        u16::MAX
+                    ),
+                )
+            })?;
+            bytes.push(JexConstantType::String as u8);
+            bytes.extend_from_slice(&length.to_le_bytes());
+            bytes.extend_from_slice(string.as_bytes());
+        }
+        JexConstant::Function {
+            chunk_id: function_chunk_id,
+        } => {
+            let function_chunk_id = u8::try_from(*function_chunk_id).map_err(|_| {
+                BytecodeSerializationError::new(
+                    chunk_id,
+                    format!(
+                        "Function constant refers to chunk #{}, at most {} is allowed",
+                        function_chunk_id,
+                        u8::MAX
+                    ),
+                )
+            })?;
+            bytes.push(JexConstantType::Function as u8);
+            bytes.push(function_chunk_id);
+        }
+        JexConstant::Float(float) => {
+            bytes.push(JexConstantType::Float as u8);
+            bytes.extend_from_slice(&float.to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::code::bytecode_header::{BytecodeHeader, DEBUG_INFO_SECTION, LOCAL_NAMES_SECTION};
+    use crate::code::debug_info::{ChunkDebugInfo, DebugInfo, LineEntry};
+    use crate::code::loader::{load_bytecode, JexBytecode};
+    use crate::code::local_names::{ChunkLocalNames, LocalName, LocalNames};
+    use crate::code::serializer::{serialize_bytecode, serialize_code};
+    use extendable_vm::{Chunk, Code};
+
+    fn chunk(constants: Vec<JexConstant>, code: Vec<u8>) -> Chunk<JexConstant> {
+        Chunk { constants, code }
+    }
+
+    fn example_code() -> Code<JexConstant> {
+        Code {
+            chunks: vec![
+                chunk(
+                    vec![
+                        JexConstant::Int(-5),
+                        JexConstant::from_str("привет"),
+                        JexConstant::Function { chunk_id: 1 },
+                        JexConstant::Float(0.1),
+                    ],
+                    vec![0, 1, 2, 3],
+                ),
+                chunk(
+                    vec![JexConstant::from_str("f"), JexConstant::Int(0)],
+                    vec![],
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn serialized_bytecode_should_be_parsed_back() {
+        let code = example_code();
+        for header in [BytecodeHeader::new(0), BytecodeHeader::legacy()] {
+            let bytecode = JexBytecode {
+                header: header.clone(),
+                code: example_code(),
+                debug_info: None,
+                local_names: None,
+            };
+            let parsed = load_bytecode(&serialize_bytecode(&bytecode).unwrap()).unwrap();
+            assert_eq!(header, parsed.header);
+            assert_eq!(2, parsed.code.chunks.len());
+            for (expected, actual) in code.chunks.iter().zip(&parsed.code.chunks) {
+                assert_eq!(expected.constants, actual.constants);
+                assert_eq!(expected.code, actual.code);
+            }
+        }
+    }
+
+    #[test]
+    fn chunk_with_more_than_255_constants_should_be_parsed_back() {
+        let constants: Vec<JexConstant> = (0..300).map(JexConstant::Int).collect();
+        let bytecode = JexBytecode {
+            header: BytecodeHeader::new(0),
+            code: Code {
+                chunks: vec![chunk(constants.clone(), vec![])],
+            },
+            debug_info: None,
+            local_names: None,
+        };
+        let parsed = load_bytecode(&serialize_bytecode(&bytecode).unwrap()).unwrap();
+        assert_eq!(constants, parsed.code.chunks[0].constants);
+    }
+
+    #[test]
+    fn optional_sections_should_be_parsed_back() {
+        let debug_info = DebugInfo {
+            chunks: vec![ChunkDebugInfo {
+                file: "main.jex".to_string(),
+                lines: vec![LineEntry { offset: 0, line: 1 }],
+            }],
+        };
+        let local_names = LocalNames {
+            chunks: vec![
+                ChunkLocalNames::default(),
+                ChunkLocalNames {
+                    locals: vec![LocalName {
+                        name: "x".to_string(),
+                        slot: 1,
+                        start: 0,
+                        end: 0,
+                    }],
+                },
+            ],
+        };
+        let bytecode = JexBytecode {
+            header: BytecodeHeader::new(0),
+            code: example_code(),
+            debug_info: Some(debug_info.clone()),
+            local_names: Some(local_names.clone()),
+        };
+        let parsed = load_bytecode(&serialize_bytecode(&bytecode).unwrap()).unwrap();
+        assert_eq!(
+            BytecodeHeader::new(DEBUG_INFO_SECTION | LOCAL_NAMES_SECTION),
+            parsed.header
+        );
+        assert_eq!(Some(debug_info), parsed.debug_info);
+        assert_eq!(Some(local_names), parsed.local_names);
+        assert_eq!(example_code().chunks[1].code, parsed.code.chunks[1].code);
+
+        let legacy = JexBytecode {
+            header: BytecodeHeader::legacy(),
+            ..bytecode
+        };
+        assert!(serialize_bytecode(&legacy).is_err());
+    }
+
+    #[test]
+    fn parsed_examples_should_be_serialized_to_the_same_bytes() {
+        let examples: [&[u8]; 2] = [
+            include_bytes!("../../examples/2_times_10.bytecode"),
+            include_bytes!("../../examples/2_times_10_with_header.bytecode"),
+        ];
+        for bytes in examples {
+            let bytecode = load_bytecode(bytes).unwrap();
+            assert_eq!(bytes.to_vec(), serialize_bytecode(&bytecode).unwrap());
+        }
+    }
+
+    #[test]
+    fn values_that_do_not_fit_should_be_reported() {
+        let serialize_with_header =
+            |constants: Vec<JexConstant>, code: Vec<u8>, header: &BytecodeHeader| {
+                serialize_code(
+                    &Code {
+                        chunks: vec![chunk(vec![], vec![]), chunk(constants, code)],
+                    },
+                    header,
+                )
+                .unwrap_err()
+                .to_string()
+            };
+        let serialize = |constants: Vec<JexConstant>, code: Vec<u8>| {
+            serialize_with_header(constants, code, &BytecodeHeader::new(0))
+        };
+        assert_eq!(
+            "Chunk #1: String constant is 65536 bytes long, at most 65535 are allowed",
+            serialize(vec![JexConstant::String("a".repeat(65536))], vec![])
+        );
+        assert_eq!(
+            "Chunk #1: Function constant refers to chunk #256, at most 255 is allowed",
+            serialize(vec![JexConstant::Function { chunk_id: 256 }], vec![])
+        );
+        assert_eq!(
+            "Chunk #1: Chunk has 65536 constants, at most 65535 are allowed",
+            serialize(vec![JexConstant::Int(0); 65536], vec![])
+        );
+        for header in [
+            BytecodeHeader::legacy(),
+            BytecodeHeader {
+                version: 1,
+                flags: 0,
+            },
+        ] {
+            assert_eq!(
+                "Chunk #1: Chunk has 256 constants, at most 255 are allowed",
+                serialize_with_header(vec![JexConstant::Int(0); 256], vec![], &header)
+            );
+        }
+        assert_eq!(
+            "Chunk #1: Chunk has 65536 bytes of code, at most 65535 are allowed",
+            serialize(vec![], vec![0; 65536])
+        );
+    }
+}
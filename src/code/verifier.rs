@@ -0,0 +1,486 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::code::decoder::{decode_chunk, DecodedInstruction};
+use crate::exceptions::static_exceptions::InvalidBytecode;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::operands::{Capture, OperandKind};
+use crate::values::values::JexFunction;
+use extendable_vm::{Chunk, Code};
+use std::collections::{HashMap, HashSet};
+
+/// Number of operands that an instruction pops from the stack and pushes onto it
+struct StackEffect {
+    pops: usize,
+    pushes: usize,
+}
+
+/// Checks the code before it is executed.
+///
+/// The verifier checks that every instruction is known and has all of its arguments,
+/// that constant indices are valid, that jumps land on instruction boundaries,
+/// that `Function` constants point at callable chunks and that no instruction pops
+/// more operands than its call frame has.
+/// Returns every problem that was found.
+pub fn verify_code(code: &Code<JexConstant>) -> Result<(), Vec<InvalidBytecode>> {
+    let mut errors = vec![];
+    for chunk_id in 0..code.chunks.len() {
+        verify_chunk(code, chunk_id, &mut errors);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn verify_chunk(code: &Code<JexConstant>, chunk_id: usize, errors: &mut Vec<InvalidBytecode>) {
+    let chunk = &code.chunks[chunk_id];
+    verify_function_constants(code, chunk_id, errors);
+    let instructions = match decode_chunk(chunk_id, chunk) {
+        Ok(instructions) => instructions,
+        Err(error) => {
+            errors.push(error);
+            return;
+        }
+    };
+    let n_errors = errors.len();
+    for instruction in &instructions {
+        verify_operands(chunk_id, chunk, &instructions, instruction, errors);
+    }
+    if errors.len() > n_errors {
+        // stack depth cannot be checked if the control flow is broken
+        return;
+    }
+    let entry_depth = if chunk_id == 0 {
+        // the script function
+        Some(1)
+    } else {
+        JexFunction::from_chunk(code, chunk_id)
+            .ok()
+            .and_then(|function| function_arity(&function))
+            .map(|arity| arity + 1)
+    };
+    if let Some(entry_depth) = entry_depth {
+        verify_stack_depth(chunk_id, chunk, &instructions, entry_depth, errors);
+    }
+}
+
+fn function_arity(function: &JexFunction) -> Option<usize> {
+    if let JexFunction::Function { arity, .. } = function {
+        Some(*arity)
+    } else {
+        None
+    }
+}
+
+fn verify_function_constants(
+    code: &Code<JexConstant>,
+    chunk_id: usize,
+    errors: &mut Vec<InvalidBytecode>,
+) {
+    for (constant_id, constant) in code.chunks[chunk_id].constants.iter().enumerate() {
+        if let JexConstant::Function {
+            chunk_id: function_chunk_id,
+        } = constant
+        {
+            let is_valid = *function_chunk_id != 0
+                && JexFunction::from_chunk(code, *function_chunk_id).is_ok();
+            if !is_valid {
+                errors.push(InvalidBytecode::in_chunk(
+                    chunk_id,
+                    format!(
+                        "Constant #{} points at chunk #{} which is not a function with a name and an arity",
+                        constant_id, function_chunk_id
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn verify_operands(
+    chunk_id: usize,
+    chunk: &Chunk<JexConstant>,
+    instructions: &[DecodedInstruction],
+    instruction: &DecodedInstruction,
+    errors: &mut Vec<InvalidBytecode>,
+) {
+    let error = |message: String| InvalidBytecode::at(chunk_id, instruction.offset, message);
+    for (operand, value) in &instruction.operands {
+        match operand.kind {
+            OperandKind::Constant => {
+                if *value >= chunk.constants.len() {
+                    errors.push(error(format!(
+                        "{} refers to constant #{} but the chunk has {} constants",
+                        instruction.name,
+                        value,
+                        chunk.constants.len()
+                    )));
+                }
+            }
+            OperandKind::Identifier => match chunk.constants.get(*value) {
+                Some(JexConstant::String(_)) => {}
+                Some(constant) => errors.push(error(format!(
+                    "{} expects constant #{} to be a string but it is {:?}",
+                    instruction.name, value, constant
+                ))),
+                None => errors.push(error(format!(
+                    "{} refers to constant #{} but the chunk has {} constants",
+                    instruction.name,
+                    value,
+                    chunk.constants.len()
+                ))),
+            },
+            OperandKind::ForwardOffset | OperandKind::BackwardOffset => {
+                let target = instruction.jump_target();
+                let is_valid = target.is_some_and(|target| {
+                    target == chunk.code.len()
+                        || instructions.iter().any(|other| other.offset == target)
+                });
+                if !is_valid {
+                    errors.push(error(format!(
+                        "{} by {} does not land on an instruction",
+                        instruction.name, value
+                    )));
+                }
+            }
+            OperandKind::LocalSlot
+            | OperandKind::Arity
+            | OperandKind::Count
+            | OperandKind::Captures
+            | OperandKind::Upvalue => {}
+        }
+    }
+}
+
+fn stack_effect(instruction: &DecodedInstruction) -> StackEffect {
+    let (pops, pushes) = match instruction.op_code {
+        JexOpCode::Constant
+        | JexOpCode::ConstantLong
+        | JexOpCode::Null
+        | JexOpCode::True
+        | JexOpCode::False
+        | JexOpCode::GetLocal
+        | JexOpCode::GetLocalLong
+        | JexOpCode::GetGlobal
+        | JexOpCode::GetGlobalLong
+        | JexOpCode::ReadLine
+        | JexOpCode::NewInstance
+        | JexOpCode::NewMap
+        | JexOpCode::GetUpvalue
+        | JexOpCode::Class
+        | JexOpCode::ClassLong => (0, 1),
+        JexOpCode::Pop
+        | JexOpCode::SetLocal
+        | JexOpCode::SetLocalLong
+        | JexOpCode::DefineGlobal
+        | JexOpCode::DefineGlobalLong
+        | JexOpCode::SetGlobal
+        | JexOpCode::SetGlobalLong
+        | JexOpCode::SetUpvalue
+        | JexOpCode::CloseUpvalue
+        | JexOpCode::Return => (1, 0),
+        JexOpCode::Print
+        | JexOpCode::Not
+        | JexOpCode::Negate
+        | JexOpCode::ToString
+        | JexOpCode::ParseInt
+        | JexOpCode::ParseFloat
+        | JexOpCode::Len
+        | JexOpCode::MapKeys
+        | JexOpCode::GetPrototype
+        | JexOpCode::BitNot
+        | JexOpCode::ToJson
+        | JexOpCode::FromJson
+        | JexOpCode::Closure
+        | JexOpCode::GetField
+        | JexOpCode::GetFieldLong
+        | JexOpCode::HasField
+        | JexOpCode::HasFieldLong
+        | JexOpCode::DeleteField
+        | JexOpCode::DeleteFieldLong
+        | JexOpCode::GetFieldOrNull
+        | JexOpCode::GetFieldOrNullLong
+        | JexOpCode::TypeOf
+        | JexOpCode::IsType
+        | JexOpCode::IsTypeLong
+        | JexOpCode::JumpForwardIfFalse
+        | JexOpCode::JumpForwardIfFalseLong => (1, 1),
+        JexOpCode::Equal
+        | JexOpCode::Greater
+        | JexOpCode::Less
+        | JexOpCode::GreaterEqual
+        | JexOpCode::LessEqual
+        | JexOpCode::NotEqual
+        | JexOpCode::Add
+        | JexOpCode::Subtract
+        | JexOpCode::Multiply
+        | JexOpCode::Divide
+        | JexOpCode::Modulo
+        | JexOpCode::BitAnd
+        | JexOpCode::BitOr
+        | JexOpCode::BitXor
+        | JexOpCode::ShiftLeft
+        | JexOpCode::ShiftRight
+        | JexOpCode::SetField
+        | JexOpCode::SetFieldLong
+        | JexOpCode::GetFieldDynamic
+        | JexOpCode::IndexGet
+        | JexOpCode::ArrayPush
+        | JexOpCode::MapGet
+        | JexOpCode::MapRemove
+        | JexOpCode::MapContains
+        | JexOpCode::Method
+        | JexOpCode::MethodLong
+        | JexOpCode::SetPrototype => (2, 1),
+        JexOpCode::IndexSet | JexOpCode::MapInsert | JexOpCode::SetFieldDynamic => (3, 1),
+        JexOpCode::JumpForward
+        | JexOpCode::JumpForwardLong
+        | JexOpCode::JumpBackward
+        | JexOpCode::JumpBackwardLong => (0, 0),
+        JexOpCode::Call => (instruction.operands[0].1 + 1, 1),
+        JexOpCode::Invoke | JexOpCode::InvokeLong => (instruction.operands[1].1 + 1, 1),
+        JexOpCode::NewArray => (instruction.operands[0].1, 1),
+    };
+    StackEffect { pops, pushes }
+}
+
+/// Offsets of instructions that can be executed right after `instruction`
+fn successors(instruction: &DecodedInstruction) -> Vec<usize> {
+    match instruction.op_code {
+        JexOpCode::Return => vec![],
+        JexOpCode::JumpForward
+        | JexOpCode::JumpForwardLong
+        | JexOpCode::JumpBackward
+        | JexOpCode::JumpBackwardLong => instruction.jump_target().into_iter().collect(),
+        JexOpCode::JumpForwardIfFalse | JexOpCode::JumpForwardIfFalseLong => {
+            let mut successors = vec![instruction.next_offset()];
+            successors.extend(instruction.jump_target());
+            successors
+        }
+        _ => vec![instruction.next_offset()],
+    }
+}
+
+/// Walks every path through the chunk and checks that the stack of the call frame never underflows.
+///
+/// Only the smallest possible stack depth of every instruction is tracked
+/// because it is the one that can lead to an underflow.
+fn verify_stack_depth(
+    chunk_id: usize,
+    chunk: &Chunk<JexConstant>,
+    instructions: &[DecodedInstruction],
+    entry_depth: usize,
+    errors: &mut Vec<InvalidBytecode>,
+) {
+    let index_by_offset: HashMap<usize, usize> = instructions
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| (instruction.offset, index))
+        .collect();
+    let mut min_depths: Vec<Option<usize>> = vec![None; instructions.len()];
+    let mut reported: HashSet<usize> = HashSet::new();
+    let mut worklist: Vec<(usize, usize)> = vec![(0, entry_depth)];
+    while let Some((offset, depth)) = worklist.pop() {
+        if offset >= chunk.code.len() {
+            // execution stops at the end of the chunk
+            continue;
+        }
+        let index = index_by_offset[&offset];
+        if min_depths[index].is_some_and(|min_depth| min_depth <= depth) {
+            continue;
+        }
+        min_depths[index] = Some(depth);
+        let instruction = &instructions[index];
+        let effect = stack_effect(instruction);
+        if let Some(message) = find_stack_problem(instruction, &effect, depth) {
+            if reported.insert(index) {
+                errors.push(InvalidBytecode::at(chunk_id, offset, message));
+            }
+            continue;
+        }
+        let new_depth = depth - effect.pops + effect.pushes;
+        for successor in successors(instruction) {
+            worklist.push((successor, new_depth));
+        }
+    }
+}
+
+fn find_stack_problem(
+    instruction: &DecodedInstruction,
+    effect: &StackEffect,
+    depth: usize,
+) -> Option<String> {
+    if depth < effect.pops {
+        return Some(format!(
+            "{} needs {} operands but the call frame may have only {}",
+            instruction.name, effect.pops, depth
+        ));
+    }
+    let operand_slots = instruction
+        .operands
+        .iter()
+        .filter(|(operand, _)| operand.kind == OperandKind::LocalSlot)
+        .map(|(_, slot)| *slot);
+    let captured_slots = instruction
+        .captures
+        .iter()
+        .filter_map(|capture| match capture {
+            Capture::Local(slot) => Some(*slot),
+            Capture::Upvalue(_) => None,
+        });
+    let local_slot = operand_slots.chain(captured_slots).max()?;
+    let frame_size = depth - effect.pops;
+    if local_slot >= frame_size {
+        Some(format!(
+            "{} refers to local #{} but the call frame may have only {} operands",
+            instruction.name, local_slot, frame_size
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::code::verifier::verify_code;
+    use crate::instructions::op_codes::JexOpCode;
+    use extendable_vm::{Chunk, Code};
+
+    fn chunk(constants: Vec<JexConstant>, code: Vec<u8>) -> Chunk<JexConstant> {
+        Chunk { constants, code }
+    }
+
+    fn verify(chunks: Vec<Chunk<JexConstant>>) -> usize {
+        verify_code(&Code { chunks })
+            .err()
+            .map_or(0, |errors| errors.len())
+    }
+
+    #[test]
+    fn valid_code_should_pass() {
+        let code = vec![
+            JexOpCode::Constant as u8,
+            0,
+            JexOpCode::Constant as u8,
+            1,
+            JexOpCode::Call as u8,
+            1,
+            JexOpCode::Print as u8,
+        ];
+        let script = chunk(
+            vec![JexConstant::Function { chunk_id: 1 }, JexConstant::Int(1)],
+            code,
+        );
+        let function = chunk(
+            vec![JexConstant::from_str("f"), JexConstant::Int(1)],
+            vec![JexOpCode::GetLocal as u8, 1, JexOpCode::Return as u8],
+        );
+        assert_eq!(0, verify(vec![script, function]));
+    }
+
+    #[test]
+    fn unknown_op_code_should_fail() {
+        assert_eq!(1, verify(vec![chunk(vec![], vec![200])]));
+    }
+
+    #[test]
+    fn missing_argument_should_fail() {
+        let code = vec![JexOpCode::Constant as u8];
+        assert_eq!(1, verify(vec![chunk(vec![JexConstant::Int(1)], code)]));
+    }
+
+    #[test]
+    fn constant_index_out_of_range_should_fail() {
+        let code = vec![JexOpCode::Constant as u8, 1];
+        assert_eq!(1, verify(vec![chunk(vec![JexConstant::Int(1)], code)]));
+    }
+
+    #[test]
+    fn get_global_of_int_constant_should_fail() {
+        let code = vec![JexOpCode::GetGlobal as u8, 0];
+        assert_eq!(1, verify(vec![chunk(vec![JexConstant::Int(1)], code)]));
+    }
+
+    #[test]
+    fn jump_into_arguments_should_fail() {
+        let code = vec![
+            JexOpCode::JumpForward as u8,
+            1,
+            JexOpCode::Constant as u8,
+            0,
+        ];
+        assert_eq!(1, verify(vec![chunk(vec![JexConstant::Int(1)], code)]));
+    }
+
+    #[test]
+    fn jump_to_the_end_of_chunk_should_pass() {
+        let code = vec![JexOpCode::JumpForward as u8, 1, JexOpCode::Null as u8];
+        assert_eq!(0, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn jump_before_code_should_fail() {
+        let code = vec![JexOpCode::Null as u8, JexOpCode::JumpBackward as u8, 10];
+        assert_eq!(1, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn function_constant_without_header_should_fail() {
+        let script = chunk(vec![JexConstant::Function { chunk_id: 1 }], vec![]);
+        let function = chunk(vec![JexConstant::Int(1)], vec![]);
+        assert_eq!(1, verify(vec![script, function]));
+    }
+
+    #[test]
+    fn function_constant_pointing_to_missing_chunk_should_fail() {
+        let script = chunk(vec![JexConstant::Function { chunk_id: 3 }], vec![]);
+        assert_eq!(1, verify(vec![script]));
+    }
+
+    #[test]
+    fn stack_underflow_should_fail() {
+        // the script function is the only operand
+        let code = vec![JexOpCode::Pop as u8, JexOpCode::Pop as u8];
+        assert_eq!(1, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn stack_underflow_on_one_branch_should_fail() {
+        let code = vec![
+            JexOpCode::True as u8,
+            JexOpCode::JumpForwardIfFalse as u8,
+            2,
+            JexOpCode::Pop as u8,
+            JexOpCode::Pop as u8,
+            // stack is empty if the jump was not taken
+            JexOpCode::Pop as u8,
+        ];
+        assert_eq!(1, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn loops_should_be_verified() {
+        let code = vec![
+            JexOpCode::True as u8,
+            JexOpCode::JumpForwardIfFalse as u8,
+            3,
+            JexOpCode::JumpBackward as u8,
+            5,
+            JexOpCode::Pop as u8,
+        ];
+        assert_eq!(0, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn get_local_outside_of_frame_should_fail() {
+        let code = vec![JexOpCode::GetLocal as u8, 1];
+        assert_eq!(1, verify(vec![chunk(vec![], code)]));
+    }
+
+    #[test]
+    fn capture_outside_of_frame_should_fail() {
+        let code = vec![JexOpCode::Null as u8, JexOpCode::Closure as u8, 1, 1, 1];
+        assert_eq!(1, verify(vec![chunk(vec![], code)]));
+    }
+}
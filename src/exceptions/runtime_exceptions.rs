@@ -122,3 +122,249 @@ impl From<FieldNotFound> for Exception {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct DivisionByZero;
+
+impl From<DivisionByZero> for Exception {
+    fn from(_exception: DivisionByZero) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "DivisionByZero".to_string(),
+            message: "Tried to divide an Int by zero".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NegativeShiftAmount(pub i32);
+
+impl From<NegativeShiftAmount> for Exception {
+    fn from(exception: NegativeShiftAmount) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "NegativeShiftAmount".to_string(),
+            message: format!("Tried to shift an Int by {} bits", exception.0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntegerOverflow(pub &'static str);
+
+impl From<IntegerOverflow> for Exception {
+    fn from(exception: IntegerOverflow) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "IntegerOverflow".to_string(),
+            message: format!("Result of operator {} does not fit into Int", exception.0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotArrayException(String);
+
+impl NotArrayException {
+    pub fn new(value: &JexValue) -> NotArrayException {
+        NotArrayException(value.get_type())
+    }
+}
+
+impl From<NotArrayException> for Exception {
+    fn from(exception: NotArrayException) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "NotArrayException".to_string(),
+            message: format!(
+                "Tried to use {} as an array but only arrays have elements",
+                exception.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidIndex(String);
+
+impl InvalidIndex {
+    pub fn new(index: &JexValue) -> InvalidIndex {
+        InvalidIndex(index.get_type())
+    }
+}
+
+impl From<InvalidIndex> for Exception {
+    fn from(exception: InvalidIndex) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "InvalidIndex".to_string(),
+            message: format!("Array index must be Int but was {}", exception.0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IndexOutOfBounds {
+    pub index: i32,
+    pub length: usize,
+}
+
+impl From<IndexOutOfBounds> for Exception {
+    fn from(exception: IndexOutOfBounds) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "IndexOutOfBounds".to_string(),
+            message: format!(
+                "Index {} is out of bounds for array of length {}",
+                exception.index, exception.length
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StringIndexOutOfBounds {
+    pub index: i32,
+    pub length: usize,
+}
+
+impl From<StringIndexOutOfBounds> for Exception {
+    fn from(exception: StringIndexOutOfBounds) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "StringIndexOutOfBounds".to_string(),
+            message: format!(
+                "Index {} is out of bounds for string of {} characters",
+                exception.index, exception.length
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotMapException(String);
+
+impl NotMapException {
+    pub fn new(value: &JexValue) -> NotMapException {
+        NotMapException(value.get_type())
+    }
+}
+
+impl From<NotMapException> for Exception {
+    fn from(exception: NotMapException) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "NotMapException".to_string(),
+            message: format!(
+                "Tried to use {} as a map but only maps have keys",
+                exception.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidMapKey(String);
+
+impl InvalidMapKey {
+    pub fn new(key: &JexValue) -> InvalidMapKey {
+        InvalidMapKey(key.get_type())
+    }
+}
+
+impl From<InvalidMapKey> for Exception {
+    fn from(exception: InvalidMapKey) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "InvalidMapKey".to_string(),
+            message: format!(
+                "Map key must be Int, String, Boolean or null but was {}",
+                exception.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyNotFound(String);
+
+impl KeyNotFound {
+    pub fn new(key: &JexValue) -> KeyNotFound {
+        KeyNotFound(format!("{:?}", key))
+    }
+}
+
+impl From<KeyNotFound> for Exception {
+    fn from(exception: KeyNotFound) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "KeyNotFound".to_string(),
+            message: format!("Key {} not found in map", exception.0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpvalueNotFound {
+    pub index: usize,
+    pub n_upvalues: usize,
+}
+
+impl From<UpvalueNotFound> for Exception {
+    fn from(exception: UpvalueNotFound) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "UpvalueNotFound".to_string(),
+            message: format!(
+                "Tried to access upvalue #{} but the current function captured {} variables",
+                exception.index, exception.n_upvalues
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PrototypeCycle(pub String);
+
+impl From<PrototypeCycle> for Exception {
+    fn from(exception: PrototypeCycle) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "PrototypeCycle".to_string(),
+            message: format!(
+                "Prototype chain is a cycle, field {} was not found in it",
+                exception.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonParseException {
+    pub message: String,
+    /// Index of the character where the error was found
+    pub position: usize,
+}
+
+impl From<JsonParseException> for Exception {
+    fn from(exception: JsonParseException) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "JsonParseException".to_string(),
+            message: format!("{} at position {}", exception.message, exception.position),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonEncodeException(pub String);
+
+impl From<JsonEncodeException> for Exception {
+    fn from(exception: JsonEncodeException) -> Self {
+        Exception {
+            exception_type: ExceptionType::Runtime,
+            name: "JsonEncodeException".to_string(),
+            message: exception.0,
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::code::bytecode_header::CURRENT_BYTECODE_VERSION;
 use extendable_vm::{Exception, ExceptionType};
+use std::fmt;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
 pub struct NotFoundChunkForFunction(pub usize);
@@ -28,3 +31,171 @@ impl From<InvalidFunctionChunk> for Exception {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct TruncatedBytecodeHeader;
+
+impl From<TruncatedBytecodeHeader> for Exception {
+    fn from(_exception: TruncatedBytecodeHeader) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "TruncatedBytecodeHeader".to_string(),
+            message: "Bytecode file ended while reading its header".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnsupportedBytecodeVersion(pub u8);
+
+impl From<UnsupportedBytecodeVersion> for Exception {
+    fn from(exception: UnsupportedBytecodeVersion) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "UnsupportedBytecodeVersion".to_string(),
+            message: format!(
+                "Bytecode format version {} is not supported, this VM supports versions 1 to {}",
+                exception.0, CURRENT_BYTECODE_VERSION
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownBytecodeFlags(pub u8);
+
+impl From<UnknownBytecodeFlags> for Exception {
+    fn from(exception: UnknownBytecodeFlags) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "UnknownBytecodeFlags".to_string(),
+            message: format!(
+                "Bytecode header has unknown section flags {:#010b}",
+                exception.0
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidBytecode {
+    pub chunk_id: usize,
+    pub offset: Option<usize>,
+    pub message: String,
+}
+
+impl InvalidBytecode {
+    pub fn in_chunk(chunk_id: usize, message: String) -> InvalidBytecode {
+        InvalidBytecode {
+            chunk_id,
+            offset: None,
+            message,
+        }
+    }
+    pub fn at(chunk_id: usize, offset: usize, message: String) -> InvalidBytecode {
+        InvalidBytecode {
+            chunk_id,
+            offset: Some(offset),
+            message,
+        }
+    }
+}
+
+impl Display for InvalidBytecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(offset) = self.offset {
+            write!(
+                f,
+                "Chunk #{} at byte {}: {}",
+                self.chunk_id, offset, self.message
+            )
+        } else {
+            write!(f, "Chunk #{}: {}", self.chunk_id, self.message)
+        }
+    }
+}
+
+impl From<InvalidBytecode> for Exception {
+    fn from(exception: InvalidBytecode) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "InvalidBytecode".to_string(),
+            message: exception.to_string(),
+        }
+    }
+}
+
+/// An error in a text assembly program, `line` starts from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblyError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl AssemblyError {
+    pub fn new(line: usize, message: String) -> AssemblyError {
+        AssemblyError { line, message }
+    }
+}
+
+impl Display for AssemblyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl From<AssemblyError> for Exception {
+    fn from(exception: AssemblyError) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "AssemblyError".to_string(),
+            message: exception.to_string(),
+        }
+    }
+}
+
+/// A value in a chunk that cannot be represented in the bytecode format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytecodeSerializationError {
+    pub chunk_id: usize,
+    pub message: String,
+}
+
+impl BytecodeSerializationError {
+    pub fn new(chunk_id: usize, message: String) -> BytecodeSerializationError {
+        BytecodeSerializationError { chunk_id, message }
+    }
+}
+
+impl Display for BytecodeSerializationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Chunk #{}: {}", self.chunk_id, self.message)
+    }
+}
+
+impl From<BytecodeSerializationError> for Exception {
+    fn from(exception: BytecodeSerializationError) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "BytecodeSerializationError".to_string(),
+            message: exception.to_string(),
+        }
+    }
+}
+
+/// An optional section of a bytecode file that could not be parsed
+#[derive(Debug)]
+pub struct InvalidBytecodeSection {
+    pub section: &'static str,
+    pub message: String,
+}
+
+impl From<InvalidBytecodeSection> for Exception {
+    fn from(exception: InvalidBytecodeSection) -> Self {
+        Exception {
+            exception_type: ExceptionType::Static,
+            name: "InvalidBytecodeSection".to_string(),
+            message: format!("Section '{}': {}", exception.section, exception.message),
+        }
+    }
+}
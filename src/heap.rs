@@ -0,0 +1,343 @@
+use crate::types::JexMachine;
+use crate::values::values::{JexArray, JexCell, JexFunction, JexInstance, JexMap, JexValue};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::{Rc, Weak};
+
+/// Settings of the collector of unreachable reference cycles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GcOptions {
+    pub enabled: bool,
+    /// Number of tracked objects that starts the first collection
+    pub threshold: usize,
+    /// The next collection starts when the number of tracked objects is this many times the number of survivors
+    pub growth_factor: usize,
+}
+
+impl Default for GcOptions {
+    fn default() -> Self {
+        GcOptions {
+            enabled: true,
+            threshold: 1024,
+            growth_factor: 2,
+        }
+    }
+}
+
+/// What the collector has done so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub collections: usize,
+    /// Objects that were allocated while the heap was active
+    pub allocated: usize,
+    /// Unreachable objects that were cleared to break their cycles
+    pub collected: usize,
+    /// Tracked objects that survived the last collection
+    pub live: usize,
+}
+
+/// An object that can reference other values and therefore be a part of a cycle
+enum TrackedObject {
+    Instance(Weak<JexInstance>),
+    Array(Weak<JexArray>),
+    Map(Weak<JexMap>),
+    Cell(Weak<JexCell>),
+}
+
+/// A tracked object that is still referenced by something
+enum LiveObject {
+    Instance(Rc<JexInstance>),
+    Array(Rc<JexArray>),
+    Map(Rc<JexMap>),
+    Cell(Rc<JexCell>),
+}
+
+struct HeapState {
+    options: GcOptions,
+    objects: Vec<TrackedObject>,
+    next_collection: usize,
+    stats: GcStats,
+}
+
+/// Collects reference cycles that values keep alive after the program can no longer reach them.
+///
+/// Values are still owned by `Rc`, the heap only tracks the objects that can reference other values.
+/// A collection marks every value reachable from the operand stack, which holds the slots of all call frames,
+/// and from `machine.globals`. Tracked objects that are not marked are cleared,
+/// so their fields, elements, entries and prototypes are dropped and the cycles between them are broken.
+///
+/// Values captured by native functions are not roots, they must also be reachable from the machine.
+pub struct JexHeap {
+    state: Rc<RefCell<HeapState>>,
+}
+
+/// Objects allocated on this thread are tracked by the heap while this guard exists
+pub struct ActiveHeap<'a> {
+    _heap: &'a JexHeap,
+}
+
+thread_local! {
+    static ACTIVE_HEAPS: RefCell<Vec<Rc<RefCell<HeapState>>>> = const { RefCell::new(vec![]) };
+}
+
+impl JexHeap {
+    pub fn new(options: GcOptions) -> JexHeap {
+        JexHeap {
+            state: Rc::new(RefCell::new(HeapState {
+                options,
+                objects: vec![],
+                next_collection: options.threshold,
+                stats: GcStats::default(),
+            })),
+        }
+    }
+    pub fn stats(&self) -> GcStats {
+        self.state.borrow().stats
+    }
+    /// Starts tracking objects that are allocated on this thread, the innermost active heap tracks them.
+    ///
+    /// Objects allocated before the heap is activated are never tracked, so they are never collected.
+    pub fn activate(&self) -> ActiveHeap<'_> {
+        ACTIVE_HEAPS.with(|heaps| heaps.borrow_mut().push(self.state.clone()));
+        ActiveHeap { _heap: self }
+    }
+    /// Whether enough objects were allocated since the last collection
+    pub fn should_collect(&self) -> bool {
+        let state = self.state.borrow();
+        state.options.enabled && state.objects.len() >= state.next_collection
+    }
+    /// Clears tracked objects that are unreachable from the machine and returns their number
+    pub fn collect(&self, machine: &JexMachine) -> usize {
+        let objects = std::mem::take(&mut self.state.borrow_mut().objects);
+        let marked = mark(roots(machine));
+        let mut survivors = vec![];
+        let mut garbage = vec![];
+        for object in objects {
+            match object.upgrade() {
+                Some(live) if marked.contains(&live.address()) => survivors.push(object),
+                Some(live) => garbage.push(live),
+                None => {}
+            }
+        }
+        // the objects are kept alive until all of them are cleared
+        for object in &garbage {
+            object.clear();
+        }
+        let collected = garbage.len();
+        drop(garbage);
+
+        let mut state = self.state.borrow_mut();
+        // objects that were allocated while the garbage was dropped are kept
+        survivors.append(&mut state.objects);
+        state.objects = survivors;
+        state.next_collection = state
+            .options
+            .threshold
+            .max(state.objects.len() * state.options.growth_factor);
+        state.stats.collections += 1;
+        state.stats.collected += collected;
+        state.stats.live = state.objects.len();
+        collected
+    }
+}
+
+impl Drop for ActiveHeap<'_> {
+    fn drop(&mut self) {
+        ACTIVE_HEAPS.with(|heaps| heaps.borrow_mut().pop());
+    }
+}
+
+/// Registers a newly allocated value in the active heap if there is one and returns the value
+pub fn track(value: JexValue) -> JexValue {
+    let object = match &value {
+        JexValue::Instance(instance) => TrackedObject::Instance(Rc::downgrade(instance)),
+        JexValue::Array(array) => TrackedObject::Array(Rc::downgrade(array)),
+        JexValue::Map(map) => TrackedObject::Map(Rc::downgrade(map)),
+        JexValue::Cell(cell) => TrackedObject::Cell(Rc::downgrade(cell)),
+        _ => return value,
+    };
+    ACTIVE_HEAPS.with(|heaps| {
+        if let Some(heap) = heaps.borrow().last() {
+            let mut heap = heap.borrow_mut();
+            heap.objects.push(object);
+            heap.stats.allocated += 1;
+        }
+    });
+    value
+}
+
+impl TrackedObject {
+    fn upgrade(&self) -> Option<LiveObject> {
+        match self {
+            TrackedObject::Instance(instance) => instance.upgrade().map(LiveObject::Instance),
+            TrackedObject::Array(array) => array.upgrade().map(LiveObject::Array),
+            TrackedObject::Map(map) => map.upgrade().map(LiveObject::Map),
+            TrackedObject::Cell(cell) => cell.upgrade().map(LiveObject::Cell),
+        }
+    }
+}
+
+impl LiveObject {
+    fn address(&self) -> *const () {
+        match self {
+            LiveObject::Instance(instance) => Rc::as_ptr(instance) as *const (),
+            LiveObject::Array(array) => Rc::as_ptr(array) as *const (),
+            LiveObject::Map(map) => Rc::as_ptr(map) as *const (),
+            LiveObject::Cell(cell) => Rc::as_ptr(cell) as *const (),
+        }
+    }
+    fn clear(&self) {
+        match self {
+            LiveObject::Instance(instance) => instance.clear(),
+            LiveObject::Array(array) => array.clear(),
+            LiveObject::Map(map) => map.clear(),
+            LiveObject::Cell(cell) => cell.set(JexValue::null()),
+        }
+    }
+}
+
+fn roots(machine: &JexMachine) -> Vec<JexValue> {
+    let mut roots: Vec<JexValue> = (0..machine.operand_stack_len())
+        .filter_map(|slot| machine.get_operand(slot).ok().cloned())
+        .collect();
+    roots.extend(machine.globals.values().cloned());
+    roots
+}
+
+/// Returns the addresses of all objects that can reference other values and are reachable from the roots.
+///
+/// Classes are marked too so that their methods are visited once.
+fn mark(roots: Vec<JexValue>) -> HashSet<*const ()> {
+    let mut marked = HashSet::new();
+    let mut pending = roots;
+    while let Some(value) = pending.pop() {
+        match &value {
+            JexValue::Instance(instance) if marked.insert(Rc::as_ptr(instance) as *const ()) => {
+                pending.extend(instance.field_values());
+                if let Some(prototype) = instance.prototype() {
+                    pending.push(JexValue::Instance(prototype));
+                }
+                if let Some(class) = instance.class() {
+                    pending.push(JexValue::Class(class.clone()));
+                }
+            }
+            JexValue::Class(class) if marked.insert(Rc::as_ptr(class) as *const ()) => {
+                pending.extend(class.methods().into_iter().map(JexValue::Function));
+            }
+            JexValue::Array(array) if marked.insert(Rc::as_ptr(array) as *const ()) => {
+                pending.extend(array.elements());
+            }
+            JexValue::Map(map) if marked.insert(Rc::as_ptr(map) as *const ()) => {
+                for (key, value) in map.entries() {
+                    pending.push(key);
+                    pending.push(value);
+                }
+            }
+            JexValue::Cell(cell) if marked.insert(Rc::as_ptr(cell) as *const ()) => {
+                pending.push(cell.get());
+            }
+            JexValue::Function(JexFunction::Closure(closure)) => {
+                pending.extend(closure.upvalues.iter().cloned().map(JexValue::Cell));
+            }
+            JexValue::Function(JexFunction::BoundMethod(bound_method)) => {
+                pending.push(bound_method.receiver.clone());
+                pending.push(JexValue::Function(bound_method.method.clone()));
+            }
+            _ => {}
+        }
+    }
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build_jex_machine;
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::heap::{track, GcOptions, JexHeap};
+    use crate::values::values::{JexCell, JexClass, JexClosure, JexFunction, JexValue};
+    use extendable_vm::{Chunk, Code};
+    use std::rc::Rc;
+
+    fn empty_code() -> Code<JexConstant> {
+        Code {
+            chunks: vec![Chunk {
+                constants: vec![],
+                code: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn it_should_clear_unreachable_cycles() {
+        let code = empty_code();
+        let machine = build_jex_machine(&code);
+        let heap = JexHeap::new(GcOptions::default());
+        let weak = {
+            let _active = heap.activate();
+            let object = JexValue::new_object();
+            object
+                .as_instance()
+                .unwrap()
+                .put_field("self".to_string(), object.clone());
+            match &object {
+                JexValue::Instance(instance) => Rc::downgrade(instance),
+                _ => unreachable!(),
+            }
+        };
+        assert!(weak.upgrade().is_some());
+        assert_eq!(1, heap.collect(&machine));
+        assert!(weak.upgrade().is_none());
+        assert_eq!(1, heap.stats().collected);
+        assert_eq!(0, heap.stats().live);
+    }
+
+    #[test]
+    fn it_should_keep_cycles_reachable_from_globals() {
+        let code = empty_code();
+        let mut machine = build_jex_machine(&code);
+        let heap = JexHeap::new(GcOptions::default());
+        {
+            let _active = heap.activate();
+            let array = JexValue::from_elements(vec![]);
+            array.as_array().unwrap().push(array.clone());
+            machine.globals.insert("array".to_string(), array);
+        }
+        assert_eq!(0, heap.collect(&machine));
+        let array = machine.globals["array"].as_array().unwrap();
+        assert_eq!(1, array.len());
+        assert_eq!(1, heap.stats().live);
+    }
+
+    #[test]
+    fn it_should_keep_cells_reachable_from_methods_of_classes() {
+        let code = empty_code();
+        let mut machine = build_jex_machine(&code);
+        let heap = JexHeap::new(GcOptions::default());
+        let cell = {
+            let _active = heap.activate();
+            let cell = Rc::new(JexCell::new(JexValue::Int(7)));
+            track(JexValue::Cell(cell.clone()));
+            let class = Rc::new(JexClass::new("Counter".to_string()));
+            let closure = JexClosure {
+                chunk_id: 0,
+                arity: 0,
+                name: "get".to_string(),
+                upvalues: vec![cell.clone()],
+            };
+            class.put_method("get".to_string(), JexFunction::Closure(Rc::new(closure)));
+            let instance = JexValue::new_instance_of(class);
+            machine.globals.insert("counter".to_string(), instance);
+            cell
+        };
+        assert_eq!(0, heap.collect(&machine));
+        assert_eq!(JexValue::Int(7), cell.get());
+        assert_eq!(2, heap.stats().live);
+    }
+
+    #[test]
+    fn it_should_not_track_objects_without_active_heap() {
+        let heap = JexHeap::new(GcOptions::default());
+        let _object = JexValue::new_object();
+        assert_eq!(0, heap.stats().allocated);
+    }
+}
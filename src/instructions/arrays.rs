@@ -0,0 +1,171 @@
+use crate::exceptions::runtime_exceptions::{
+    ExpectedInstructionArgument, IndexOutOfBounds, InvalidIndex, NotArrayException,
+    UnaryOperatorNotDefined,
+};
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::types::JexInstruction;
+use crate::types::JexMachine;
+use crate::values::values::{JexArray, JexValue};
+use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
+use std::convert::TryFrom;
+
+pub const NEW_ARRAY_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::NewArray as u8,
+    name: "NEW_ARRAY",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: new_array_instruction,
+    },
+};
+
+pub const INDEX_GET_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::IndexGet as u8,
+    name: "INDEX_GET",
+    instruction_fn: InstructionFn::BinaryOp(index_get),
+};
+
+pub const INDEX_SET_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::IndexSet as u8,
+    name: "INDEX_SET",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: index_set_instruction,
+    },
+};
+
+pub const ARRAY_PUSH_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ArrayPush as u8,
+    name: "ARRAY_PUSH",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: array_push_instruction,
+    },
+};
+
+pub const LEN_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Len as u8,
+    name: "LEN",
+    instruction_fn: InstructionFn::UnaryOp(len),
+};
+
+/// Pops `n` values and pushes an array that contains them in the order they were pushed
+fn new_array_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let n_elements = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    let mut elements = vec![];
+    for _ in 0..n_elements {
+        elements.push(machine.pop_operand()?);
+    }
+    elements.reverse();
+    machine.push_operand(JexValue::from_elements(elements));
+    Ok(())
+}
+
+fn index_get(array: JexValue, index: JexValue) -> Result<JexValue, Exception> {
+    let array = as_array(&array)?;
+    let index = checked_index(array, &index)?;
+    // the index has just been checked
+    Ok(array.get(index).unwrap())
+}
+
+/// Pops the value and the index and sets the element, the array stays on the stack
+fn index_set_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let value = machine.pop_operand()?;
+    let index = machine.pop_operand()?;
+    let array = as_array(machine.peek_operand()?)?;
+    let index = checked_index(array, &index)?;
+    array.set(index, value);
+    Ok(())
+}
+
+/// Pops the value and appends it to the array, the array stays on the stack
+fn array_push_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let value = machine.pop_operand()?;
+    let array = as_array(machine.peek_operand()?)?;
+    array.push(value);
+    Ok(())
+}
+
+/// Number of elements of an array, number of entries of a map or number of characters of a string
+fn len(value: JexValue) -> Result<JexValue, Exception> {
+    let length = if let Some(array) = value.as_array() {
+        array.len()
+    } else if let Some(map) = value.as_map() {
+        map.len()
+    } else if let Some(string) = value.as_string() {
+        string.chars().count()
+    } else {
+        return Err(Exception::from(UnaryOperatorNotDefined::new("len", &value)));
+    };
+    i32::try_from(length)
+        .map(JexValue::Int)
+        .map_err(|_| Exception::from(UnaryOperatorNotDefined::new("len", &value)))
+}
+
+fn as_array(value: &JexValue) -> Result<&JexArray, NotArrayException> {
+    value
+        .as_array()
+        .ok_or_else(|| NotArrayException::new(value))
+}
+
+fn checked_index(array: &JexArray, index: &JexValue) -> Result<usize, Exception> {
+    let index = index.as_int().ok_or_else(|| InvalidIndex::new(index))?;
+    let length = array.len();
+    match usize::try_from(index) {
+        Ok(checked) if checked < length => Ok(checked),
+        _ => Err(Exception::from(IndexOutOfBounds { index, length })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::arrays::{index_get, len};
+    use crate::values::values::JexValue;
+
+    fn array() -> JexValue {
+        JexValue::from_elements(vec![
+            JexValue::Int(1),
+            JexValue::from_string("a".to_string()),
+        ])
+    }
+
+    #[test]
+    fn index_get_should_return_elements() {
+        assert_eq!(
+            JexValue::Int(1),
+            index_get(array(), JexValue::Int(0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn index_get_should_fail_for_invalid_indexes() {
+        let name = |index| index_get(array(), index).unwrap_err().name;
+        assert_eq!("IndexOutOfBounds", name(JexValue::Int(2)));
+        assert_eq!("IndexOutOfBounds", name(JexValue::Int(-1)));
+        assert_eq!("InvalidIndex", name(JexValue::Float(0.0)));
+        assert_eq!(
+            "NotArrayException",
+            index_get(JexValue::Int(1), JexValue::Int(0))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn len_should_count_elements_and_characters() {
+        assert_eq!(JexValue::Int(2), len(array()).unwrap());
+        assert_eq!(
+            JexValue::Int(3),
+            len(JexValue::from_string("абв".to_string())).unwrap()
+        );
+        assert!(len(JexValue::Int(1)).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::exceptions::runtime_exceptions::{ExpectedInstructionArgument, TypeException};
+use crate::instructions::jumps::call_value;
+use crate::instructions::objects::get_property;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::types::JexInstruction;
+use crate::types::JexMachine;
+use crate::values::get_type::GetType;
+use crate::values::values::{JexClass, JexFunction, JexValue};
+use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
+use std::rc::Rc;
+
+pub const CLASS_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Class as u8,
+    name: "CLASS",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: class_instruction,
+    },
+};
+
+pub const CLASS_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ClassLong as u8,
+    name: "CLASS_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: class_long_instruction,
+    },
+};
+
+pub const METHOD_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Method as u8,
+    name: "METHOD",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: method_instruction,
+    },
+};
+
+pub const METHOD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MethodLong as u8,
+    name: "METHOD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: method_long_instruction,
+    },
+};
+
+pub const INVOKE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Invoke as u8,
+    name: "INVOKE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: invoke_instruction,
+    },
+};
+
+pub const INVOKE_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::InvokeLong as u8,
+    name: "INVOKE_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 3,
+        instruction_fn: invoke_long_instruction,
+    },
+};
+
+fn class_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    class(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn class_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    class(machine, args.chunk_id, usize::from(constant_id))
+}
+
+/// Pushes a new class without methods
+fn class(machine: &mut JexMachine, chunk_id: usize, constant_id: usize) -> Result<(), Exception> {
+    let name = machine
+        .code
+        .get_constant(chunk_id, constant_id)?
+        .as_string()?;
+    machine.push_operand(JexValue::Class(Rc::new(JexClass::new(name))));
+    Ok(())
+}
+
+fn method_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    method(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn method_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    method(machine, args.chunk_id, usize::from(constant_id))
+}
+
+/// Pops a function and adds it to the methods of the class, the class stays on the stack
+fn method(machine: &mut JexMachine, chunk_id: usize, constant_id: usize) -> Result<(), Exception> {
+    let name = machine
+        .code
+        .get_constant(chunk_id, constant_id)?
+        .as_string()?;
+    let method = machine.pop_operand()?;
+    let class = machine.peek_operand()?;
+    let class = match class {
+        JexValue::Class(class) => class,
+        _ => {
+            return Err(Exception::from(TypeException(format!(
+                "Methods can only be added to classes but {} was given",
+                class.get_type()
+            ))))
+        }
+    };
+    match method {
+        // slot 0 of methods is the receiver, so they cannot be closures that find their upvalues there
+        JexValue::Function(function @ JexFunction::Function { .. }) => {
+            class.put_method(name, function);
+            Ok(())
+        }
+        _ => Err(Exception::from(TypeException(format!(
+            "Method {} must be a function without captured variables",
+            name
+        )))),
+    }
+}
+
+fn invoke_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    let arity = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    invoke(
+        machine,
+        args.chunk_id,
+        usize::from(constant_id),
+        usize::from(arity),
+    )
+}
+
+fn invoke_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    let arity = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    invoke(
+        machine,
+        args.chunk_id,
+        usize::from(constant_id),
+        usize::from(arity),
+    )
+}
+
+/// Calls the method `name` of the receiver that is below the `arity` arguments.
+///
+/// Same as `GET_FIELD name` followed by `CALL arity` but the receiver does not have to be moved.
+fn invoke(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+    arity: usize,
+) -> Result<(), Exception> {
+    let name = machine
+        .code
+        .get_constant(chunk_id, constant_id)?
+        .as_string()?;
+    let receiver = machine.get_operand_from_top(arity)?.clone();
+    let callee = get_property(&receiver, &name)?;
+    if !matches!(callee, JexValue::Function(JexFunction::BoundMethod(_))) {
+        // other callees, such as closures stored in fields, find themselves in slot 0 like after `GET_FIELD`
+        let callee_slot = machine.operand_stack_len() - 1 - arity;
+        machine.set_operand(callee_slot, callee.clone())?;
+    }
+    call_value(machine, &callee, arity)
+}
@@ -0,0 +1,156 @@
+use crate::exceptions::runtime_exceptions::{
+    ExpectedInstructionArgument, TypeException, UpvalueNotFound,
+};
+use crate::heap;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::operands::Capture;
+use crate::instructions::types::JexInstruction;
+use crate::types::JexMachine;
+use crate::values::to_output_string::ToOutputString;
+use crate::values::values::{JexCell, JexClosure, JexFunction, JexValue};
+use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
+use std::rc::Rc;
+
+pub const CLOSURE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Closure as u8,
+    name: "CLOSURE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: closure_instruction,
+    },
+};
+
+pub const GET_UPVALUE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetUpvalue as u8,
+    name: "GET_UPVALUE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: get_upvalue_instruction,
+    },
+};
+
+pub const SET_UPVALUE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetUpvalue as u8,
+    name: "SET_UPVALUE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: set_upvalue_instruction,
+    },
+};
+
+pub const CLOSE_UPVALUE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::CloseUpvalue as u8,
+    name: "CLOSE_UPVALUE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: close_upvalue_instruction,
+    },
+};
+
+/// Pops a function and pushes a closure of it.
+///
+/// The captures follow the number of captures in the code, the instruction pointer is moved past them.
+fn closure_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let n_captures = usize::from(machine.read(&mut args).ok_or(ExpectedInstructionArgument)?);
+    let mut captures = vec![];
+    for _ in 0..n_captures {
+        let kind = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+        let index = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+        let capture = Capture::read(&[kind, index])
+            .ok_or_else(|| TypeException(format!("Unknown capture kind {}", kind)))?;
+        captures.push(capture);
+    }
+    machine
+        .instruction_pointer()?
+        .jump_forward(n_captures * Capture::N_BYTES);
+    let function = machine.pop_operand()?;
+    let (chunk_id, arity, name) = match function.as_function() {
+        Some(JexFunction::Function {
+            chunk_id,
+            arity,
+            name,
+        }) => (*chunk_id, *arity, name.clone()),
+        _ => {
+            return Err(Exception::from(TypeException(format!(
+                "Cannot create a closure of {}",
+                function.to_output_string()
+            ))))
+        }
+    };
+    let frame_start = machine.peek_frame()?.start_slot;
+    let mut upvalues = vec![];
+    for capture in captures {
+        let cell = match capture {
+            Capture::Local(slot) => capture_local(machine, frame_start + slot)?,
+            Capture::Upvalue(index) => upvalue(machine, index)?,
+        };
+        upvalues.push(cell);
+    }
+    let closure = JexClosure {
+        chunk_id,
+        arity,
+        name,
+        upvalues,
+    };
+    machine.push_operand(JexValue::Function(JexFunction::Closure(Rc::new(closure))));
+    Ok(())
+}
+
+fn get_upvalue_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let index = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    let value = upvalue(machine, usize::from(index))?.get();
+    machine.push_operand(value);
+    Ok(())
+}
+
+fn set_upvalue_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let index = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    let value = machine.pop_operand()?;
+    upvalue(machine, usize::from(index))?.set(value);
+    Ok(())
+}
+
+/// Pops a local variable that goes out of scope, closures that captured it keep its cell
+fn close_upvalue_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    machine.pop_operand()?;
+    Ok(())
+}
+
+/// Returns the cell of the local variable in `absolute_slot`, moving the variable into a new cell if needed
+fn capture_local(machine: &mut JexMachine, absolute_slot: usize) -> Result<Rc<JexCell>, Exception> {
+    let value = machine.get_operand(absolute_slot)?.clone();
+    if let JexValue::Cell(cell) = value {
+        return Ok(cell);
+    }
+    let cell = Rc::new(JexCell::new(value));
+    machine.set_operand(absolute_slot, heap::track(JexValue::Cell(cell.clone())))?;
+    Ok(cell)
+}
+
+/// Returns a variable captured by the closure of the current call frame
+fn upvalue(machine: &JexMachine, index: usize) -> Result<Rc<JexCell>, Exception> {
+    let frame_start = machine.peek_frame()?.start_slot;
+    let callee = machine.get_operand(frame_start)?.dereference();
+    let upvalues = match callee.as_function() {
+        Some(JexFunction::Closure(closure)) => closure.upvalues.as_slice(),
+        _ => &[],
+    };
+    upvalues.get(index).cloned().ok_or_else(|| {
+        Exception::from(UpvalueNotFound {
+            index,
+            n_upvalues: upvalues.len(),
+        })
+    })
+}
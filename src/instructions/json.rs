@@ -0,0 +1,32 @@
+use crate::exceptions::runtime_exceptions::UnaryOperatorNotDefined;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::types::JexInstruction;
+use crate::values::json::{from_json, to_json};
+use crate::values::values::JexValue;
+use extendable_vm::{Exception, Instruction, InstructionFn};
+
+pub const TO_JSON_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ToJson as u8,
+    name: "TO_JSON",
+    instruction_fn: InstructionFn::UnaryOp(to_json_string),
+};
+
+pub const FROM_JSON_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::FromJson as u8,
+    name: "FROM_JSON",
+    instruction_fn: InstructionFn::UnaryOp(from_json_string),
+};
+
+fn to_json_string(value: JexValue) -> Result<JexValue, Exception> {
+    Ok(JexValue::from_string(to_json(&value)?))
+}
+
+fn from_json_string(value: JexValue) -> Result<JexValue, Exception> {
+    match value.as_string() {
+        Some(json) => Ok(from_json(json)?),
+        None => Err(Exception::from(UnaryOperatorNotDefined::new(
+            "FROM_JSON",
+            &value,
+        ))),
+    }
+}
@@ -3,7 +3,7 @@ use crate::instructions::op_codes::JexOpCode;
 use crate::instructions::types::JexInstruction;
 use crate::types::JexMachine;
 use crate::values::to_output_string::ToOutputString;
-use crate::values::values::JexFunction;
+use crate::values::values::{JexFunction, JexNativeFunction, JexValue, NativeArity};
 use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
 
 pub const JUMP_FORWARD_INSTRUCTION: JexInstruction = Instruction {
@@ -15,6 +15,15 @@ pub const JUMP_FORWARD_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const JUMP_FORWARD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::JumpForwardLong as u8,
+    name: "JUMP_FORWARD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: jump_forward_long_instruction,
+    },
+};
+
 pub const JUMP_FORWARD_IF_FALSE_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::JumpForwardIfFalse as u8,
     name: "JUMP_FORWARD_IF_FALSE",
@@ -24,6 +33,15 @@ pub const JUMP_FORWARD_IF_FALSE_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const JUMP_FORWARD_IF_FALSE_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::JumpForwardIfFalseLong as u8,
+    name: "JUMP_FORWARD_IF_FALSE_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: jump_forward_if_false_long_instruction,
+    },
+};
+
 pub const JUMP_BACKWARD: JexInstruction = Instruction {
     op_code: JexOpCode::JumpBackward as u8,
     name: "JUMP_BACKWARD",
@@ -33,6 +51,15 @@ pub const JUMP_BACKWARD: JexInstruction = Instruction {
     },
 };
 
+pub const JUMP_BACKWARD_LONG: JexInstruction = Instruction {
+    op_code: JexOpCode::JumpBackwardLong as u8,
+    name: "JUMP_BACKWARD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: jump_backward_long_instruction,
+    },
+};
+
 pub const CALL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::Call as u8,
     name: "CALL",
@@ -56,10 +83,17 @@ fn jump_forward_instruction(
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let offset = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    machine
-        .instruction_pointer()?
-        .jump_forward(usize::from(offset));
-    Ok(())
+    jump_forward(machine, usize::from(offset))
+}
+
+fn jump_forward_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let offset = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    jump_forward(machine, usize::from(offset))
 }
 
 fn jump_forward_if_false_instruction(
@@ -67,26 +101,55 @@ fn jump_forward_if_false_instruction(
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let offset = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    jump_forward_if_false(machine, usize::from(offset))
+}
+
+fn jump_forward_if_false_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let offset = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    jump_forward_if_false(machine, usize::from(offset))
+}
+
+fn jump_backward_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let offset = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    jump_backward(machine, usize::from(offset))
+}
+
+fn jump_backward_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let offset = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    jump_backward(machine, usize::from(offset))
+}
+
+fn jump_forward(machine: &mut JexMachine, offset: usize) -> Result<(), Exception> {
+    machine.instruction_pointer()?.jump_forward(offset);
+    Ok(())
+}
+
+fn jump_forward_if_false(machine: &mut JexMachine, offset: usize) -> Result<(), Exception> {
     let value = machine
         .peek_operand()?
         .as_bool()
         .ok_or_else(|| TypeException("Jump forward if false condition was not bool".to_string()))?;
     if !value {
-        machine
-            .instruction_pointer()?
-            .jump_forward(usize::from(offset));
+        machine.instruction_pointer()?.jump_forward(offset);
     }
     Ok(())
 }
 
-fn jump_backward_instruction(
-    machine: &mut JexMachine,
-    mut args: InstructionPointer,
-) -> Result<(), Exception> {
-    let offset = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    machine
-        .instruction_pointer()?
-        .jump_backward(usize::from(offset));
+fn jump_backward(machine: &mut JexMachine, offset: usize) -> Result<(), Exception> {
+    machine.instruction_pointer()?.jump_backward(offset);
     Ok(())
 }
 
@@ -96,19 +159,56 @@ fn call_instruction(
 ) -> Result<(), Exception> {
     let arity = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
     let arity = usize::from(arity);
-    let function = machine
-        .get_operand_from_top(arity)?
-        .as_function()
-        .ok_or_else(|| TypeException("Value was not callable".to_string()))?;
-    if let JexFunction::Function {
-        chunk_id,
-        arity: actual_arity,
-        name,
-    } = function
-    {
-        if arity == *actual_arity {
-            let chunk_start_slot = machine.operand_stack_len() - 1 - arity;
-            machine.push_frame(*chunk_id, name.clone(), chunk_start_slot);
+    let callee = machine.get_operand_from_top(arity)?.clone();
+    call_value(machine, &callee, arity)
+}
+
+/// Calls `callee` with the `arity` arguments on top of the stack, the callee is right below them.
+///
+/// Calling a class creates an instance of it which is passed to the `init` method if the class has one.
+/// The value returned by `init` replaces the instance, so `init` must return slot 0.
+pub fn call_value(
+    machine: &mut JexMachine,
+    callee: &JexValue,
+    arity: usize,
+) -> Result<(), Exception> {
+    match callee {
+        JexValue::Function(function) => call_function(machine, function, arity),
+        JexValue::Class(class) => {
+            let instance = JexValue::new_instance_of(class.clone());
+            let callee_slot = machine.operand_stack_len() - 1 - arity;
+            machine.set_operand(callee_slot, instance)?;
+            match class.get_method("init") {
+                Some(init) => call_function(machine, &init, arity),
+                None if arity == 0 => Ok(()),
+                None => Err(Exception::from(TypeException(format!(
+                    "Class {} does not have an init method but received {} arguments",
+                    class.name, arity
+                )))),
+            }
+        }
+        _ => Err(Exception::from(TypeException(
+            "Value was not callable".to_string(),
+        ))),
+    }
+}
+
+fn call_function(
+    machine: &mut JexMachine,
+    function: &JexFunction,
+    arity: usize,
+) -> Result<(), Exception> {
+    if let JexFunction::Native(native) = function {
+        return call_native(machine, function, native, arity);
+    }
+    let chunk_start_slot = machine.operand_stack_len() - 1 - arity;
+    if let JexFunction::BoundMethod(bound) = function {
+        // methods find the receiver in slot 0
+        machine.set_operand(chunk_start_slot, bound.receiver.clone())?;
+    }
+    if let Some((chunk_id, actual_arity, name)) = function.signature() {
+        if arity == actual_arity {
+            machine.push_frame(chunk_id, name.to_string(), chunk_start_slot);
             Ok(())
         } else {
             Err(Exception::from(TypeException(format!(
@@ -126,6 +226,34 @@ fn call_instruction(
     }
 }
 
+/// Pops the arguments and the native function and pushes the result of the call
+fn call_native(
+    machine: &mut JexMachine,
+    function: &JexFunction,
+    native: &JexNativeFunction,
+    arity: usize,
+) -> Result<(), Exception> {
+    if let NativeArity::Fixed(actual_arity) = native.arity {
+        if arity != actual_arity {
+            return Err(Exception::from(TypeException(format!(
+                "Function {} has {} parameters but received {}",
+                function.to_output_string(),
+                actual_arity,
+                arity
+            ))));
+        }
+    }
+    let mut args = vec![];
+    for _ in 0..arity {
+        args.push(machine.pop_operand()?);
+    }
+    args.reverse();
+    machine.pop_operand()?;
+    let result = (native.function)(machine, args)?;
+    machine.push_operand(result);
+    Ok(())
+}
+
 fn return_instruction(
     machine: &mut JexMachine,
     mut _args: InstructionPointer,
@@ -13,14 +13,39 @@ pub const CONSTANT_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const CONSTANT_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ConstantLong as u8,
+    name: "CONSTANT_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: constant_long_instruction,
+    },
+};
+
 fn constant_instruction(
     machine: &mut JexMachine,
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    let constant = machine
-        .code
-        .get_constant(args.chunk_id, usize::from(constant_id))?;
+    load_constant(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn constant_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    load_constant(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn load_constant(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<(), Exception> {
+    let constant = machine.code.get_constant(chunk_id, constant_id)?;
     machine.push_operand(constant.to_value(machine)?);
     Ok(())
 }
@@ -0,0 +1,147 @@
+use crate::exceptions::runtime_exceptions::{KeyNotFound, NotMapException};
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::types::JexInstruction;
+use crate::types::JexMachine;
+use crate::values::values::{JexMap, JexValue};
+use extendable_vm::{Exception, Instruction, InstructionFn, InstructionPointer};
+
+pub const NEW_MAP_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::NewMap as u8,
+    name: "NEW_MAP",
+    instruction_fn: InstructionFn::Const(JexValue::new_map),
+};
+
+pub const MAP_INSERT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MapInsert as u8,
+    name: "MAP_INSERT",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: map_insert_instruction,
+    },
+};
+
+pub const MAP_GET_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MapGet as u8,
+    name: "MAP_GET",
+    instruction_fn: InstructionFn::BinaryOp(map_get),
+};
+
+pub const MAP_REMOVE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MapRemove as u8,
+    name: "MAP_REMOVE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: map_remove_instruction,
+    },
+};
+
+pub const MAP_CONTAINS_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MapContains as u8,
+    name: "MAP_CONTAINS",
+    instruction_fn: InstructionFn::BinaryOp(map_contains),
+};
+
+pub const MAP_KEYS_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::MapKeys as u8,
+    name: "MAP_KEYS",
+    instruction_fn: InstructionFn::UnaryOp(map_keys),
+};
+
+/// Pops the value and the key and inserts the entry, the map stays on the stack
+fn map_insert_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let value = machine.pop_operand()?;
+    let key = machine.pop_operand()?;
+    let map = as_map(machine.peek_operand()?)?;
+    map.insert(key, value)?;
+    Ok(())
+}
+
+fn map_get(map: JexValue, key: JexValue) -> Result<JexValue, Exception> {
+    as_map(&map)?
+        .get(&key)?
+        .ok_or_else(|| Exception::from(KeyNotFound::new(&key)))
+}
+
+/// Pops the key and removes its entry if it exists, the map stays on the stack
+fn map_remove_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let key = machine.pop_operand()?;
+    let map = as_map(machine.peek_operand()?)?;
+    map.remove(&key)?;
+    Ok(())
+}
+
+fn map_contains(map: JexValue, key: JexValue) -> Result<JexValue, Exception> {
+    Ok(JexValue::Bool(as_map(&map)?.contains_key(&key)?))
+}
+
+/// Array of the keys in the order they were inserted
+fn map_keys(map: JexValue) -> Result<JexValue, Exception> {
+    Ok(JexValue::from_elements(as_map(&map)?.keys()))
+}
+
+fn as_map(value: &JexValue) -> Result<&JexMap, NotMapException> {
+    value.as_map().ok_or_else(|| NotMapException::new(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::maps::{map_contains, map_get, map_keys};
+    use crate::values::values::JexValue;
+
+    fn map() -> JexValue {
+        let map = JexValue::new_map();
+        let entries = map.as_map().unwrap();
+        entries
+            .insert(JexValue::from_string("b".to_string()), JexValue::Int(1))
+            .unwrap();
+        entries
+            .insert(JexValue::from_string("a".to_string()), JexValue::Int(2))
+            .unwrap();
+        map
+    }
+
+    #[test]
+    fn map_get_should_return_values() {
+        assert_eq!(
+            JexValue::Int(2),
+            map_get(map(), JexValue::from_string("a".to_string())).unwrap()
+        );
+    }
+
+    #[test]
+    fn map_get_should_fail_for_missing_and_invalid_keys() {
+        let name = |key| map_get(map(), key).unwrap_err().name;
+        assert_eq!("KeyNotFound", name(JexValue::from_string("c".to_string())));
+        assert_eq!("InvalidMapKey", name(JexValue::Float(1.5)));
+        assert_eq!(
+            "NotMapException",
+            map_get(JexValue::Int(1), JexValue::Int(1))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn map_contains_should_check_keys() {
+        assert_eq!(
+            JexValue::Bool(true),
+            map_contains(map(), JexValue::from_string("b".to_string())).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            map_contains(map(), JexValue::Int(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn map_keys_should_be_in_insertion_order() {
+        let keys = map_keys(map()).unwrap();
+        assert_eq!("[\"b\", \"a\"]", format!("{:?}", keys));
+    }
+}
@@ -1,32 +1,75 @@
+use crate::instructions::arrays::{
+    ARRAY_PUSH_INSTRUCTION, INDEX_GET_INSTRUCTION, INDEX_SET_INSTRUCTION, LEN_INSTRUCTION,
+    NEW_ARRAY_INSTRUCTION,
+};
+use crate::instructions::classes::{
+    CLASS_INSTRUCTION, CLASS_LONG_INSTRUCTION, INVOKE_INSTRUCTION, INVOKE_LONG_INSTRUCTION,
+    METHOD_INSTRUCTION, METHOD_LONG_INSTRUCTION,
+};
+use crate::instructions::closures::{
+    CLOSE_UPVALUE_INSTRUCTION, CLOSURE_INSTRUCTION, GET_UPVALUE_INSTRUCTION,
+    SET_UPVALUE_INSTRUCTION,
+};
+use crate::instructions::json::{FROM_JSON_INSTRUCTION, TO_JSON_INSTRUCTION};
 use crate::instructions::jumps::{
-    CALL_INSTRUCTION, JUMP_BACKWARD, JUMP_FORWARD_IF_FALSE_INSTRUCTION, JUMP_FORWARD_INSTRUCTION,
-    RETURN_INSTRUCTION,
+    CALL_INSTRUCTION, JUMP_BACKWARD, JUMP_BACKWARD_LONG, JUMP_FORWARD_IF_FALSE_INSTRUCTION,
+    JUMP_FORWARD_IF_FALSE_LONG_INSTRUCTION, JUMP_FORWARD_INSTRUCTION,
+    JUMP_FORWARD_LONG_INSTRUCTION, RETURN_INSTRUCTION,
+};
+use crate::instructions::literal::{CONSTANT_INSTRUCTION, CONSTANT_LONG_INSTRUCTION};
+use crate::instructions::maps::{
+    MAP_CONTAINS_INSTRUCTION, MAP_GET_INSTRUCTION, MAP_INSERT_INSTRUCTION, MAP_KEYS_INSTRUCTION,
+    MAP_REMOVE_INSTRUCTION, NEW_MAP_INSTRUCTION,
 };
-use crate::instructions::literal::CONSTANT_INSTRUCTION;
 use crate::instructions::objects::{
-    GET_FIELD_INSTRUCTION, NEW_INSTANCE_INSTRUCTION, SET_FIELD_INSTRUCTION,
+    DELETE_FIELD_INSTRUCTION, DELETE_FIELD_LONG_INSTRUCTION, GET_FIELD_DYNAMIC_INSTRUCTION,
+    GET_FIELD_INSTRUCTION, GET_FIELD_LONG_INSTRUCTION, GET_FIELD_OR_NULL_INSTRUCTION,
+    GET_FIELD_OR_NULL_LONG_INSTRUCTION, GET_PROTOTYPE_INSTRUCTION, HAS_FIELD_INSTRUCTION,
+    HAS_FIELD_LONG_INSTRUCTION, NEW_INSTANCE_INSTRUCTION, SET_FIELD_DYNAMIC_INSTRUCTION,
+    SET_FIELD_INSTRUCTION, SET_FIELD_LONG_INSTRUCTION, SET_PROTOTYPE_INSTRUCTION,
 };
+use crate::instructions::operator_implementations::ArithmeticMode;
 use crate::instructions::operators::{
-    ADD_INSTRUCTION, DIVIDE_INSTRUCTION, EQUAL_INSTRUCTION, FALSE_INSTRUCTION, GREATER_INSTRUCTION,
-    LESS_INSTRUCTION, MULTIPLY_INSTRUCTION, NEGATE_INSTRUCTION, NOT_INSTRUCTION, NULL_INSTRUCTION,
-    PARSE_INT_INSTRUCTION, PRINT_INSTRUCTION, READ_LINE_INSTRUCTION, SUBTRACT_INSTRUCTION,
-    TO_STRING_INSTRUCTION, TRUE_INSTRUCTION,
+    ADD_INSTRUCTION, BIT_AND_INSTRUCTION, BIT_NOT_INSTRUCTION, BIT_OR_INSTRUCTION,
+    BIT_XOR_INSTRUCTION, DIVIDE_INSTRUCTION, EQUAL_INSTRUCTION, FALSE_INSTRUCTION,
+    GREATER_EQUAL_INSTRUCTION, GREATER_INSTRUCTION, LESS_EQUAL_INSTRUCTION, LESS_INSTRUCTION,
+    MODULO_INSTRUCTION, MULTIPLY_INSTRUCTION, NEGATE_INSTRUCTION, NOT_EQUAL_INSTRUCTION,
+    NOT_INSTRUCTION, NULL_INSTRUCTION, PARSE_FLOAT_INSTRUCTION, PARSE_INT_INSTRUCTION,
+    PRINT_INSTRUCTION, READ_LINE_INSTRUCTION, SATURATING_ADD_INSTRUCTION,
+    SATURATING_DIVIDE_INSTRUCTION, SATURATING_MULTIPLY_INSTRUCTION, SATURATING_NEGATE_INSTRUCTION,
+    SATURATING_SUBTRACT_INSTRUCTION, SHIFT_LEFT_INSTRUCTION, SHIFT_RIGHT_INSTRUCTION,
+    SUBTRACT_INSTRUCTION, TO_STRING_INSTRUCTION, TRUE_INSTRUCTION, WRAPPING_ADD_INSTRUCTION,
+    WRAPPING_DIVIDE_INSTRUCTION, WRAPPING_MULTIPLY_INSTRUCTION, WRAPPING_NEGATE_INSTRUCTION,
+    WRAPPING_SUBTRACT_INSTRUCTION,
+};
+use crate::instructions::type_checks::{
+    IS_TYPE_INSTRUCTION, IS_TYPE_LONG_INSTRUCTION, TYPE_OF_INSTRUCTION,
 };
 use crate::instructions::types::JexInstruction;
 use crate::instructions::variable::{
-    DEFINE_GLOBAL_INSTRUCTION, GET_GLOBAL_INSTRUCTION, GET_LOCAL_INSTRUCTION, POP_INSTRUCTION,
-    SET_GLOBAL_INSTRUCTION, SET_LOCAL_INSTRUCTION,
+    DEFINE_GLOBAL_INSTRUCTION, DEFINE_GLOBAL_LONG_INSTRUCTION, GET_GLOBAL_INSTRUCTION,
+    GET_GLOBAL_LONG_INSTRUCTION, GET_LOCAL_INSTRUCTION, GET_LOCAL_LONG_INSTRUCTION,
+    POP_INSTRUCTION, SET_GLOBAL_INSTRUCTION, SET_GLOBAL_LONG_INSTRUCTION, SET_LOCAL_INSTRUCTION,
+    SET_LOCAL_LONG_INSTRUCTION,
 };
+use lazy_static::lazy_static;
 
+mod arrays;
+mod classes;
+mod closures;
+mod json;
 mod jumps;
 mod literal;
+mod maps;
 mod objects;
 pub mod op_codes;
+pub mod operands;
 pub mod operator_implementations;
 mod operators;
+mod type_checks;
 mod variable;
 
-pub const JEX_INSTRUCTIONS: [&JexInstruction; 31] = [
+pub const JEX_INSTRUCTIONS: [&JexInstruction; 89] = [
     &NEGATE_INSTRUCTION,
     &ADD_INSTRUCTION,
     &SUBTRACT_INSTRUCTION,
@@ -58,8 +101,112 @@ pub const JEX_INSTRUCTIONS: [&JexInstruction; 31] = [
     &NEW_INSTANCE_INSTRUCTION,
     &GET_FIELD_INSTRUCTION,
     &SET_FIELD_INSTRUCTION,
+    &PARSE_FLOAT_INSTRUCTION,
+    &CONSTANT_LONG_INSTRUCTION,
+    &GET_LOCAL_LONG_INSTRUCTION,
+    &SET_LOCAL_LONG_INSTRUCTION,
+    &GET_GLOBAL_LONG_INSTRUCTION,
+    &DEFINE_GLOBAL_LONG_INSTRUCTION,
+    &SET_GLOBAL_LONG_INSTRUCTION,
+    &GET_FIELD_LONG_INSTRUCTION,
+    &SET_FIELD_LONG_INSTRUCTION,
+    &JUMP_FORWARD_LONG_INSTRUCTION,
+    &JUMP_FORWARD_IF_FALSE_LONG_INSTRUCTION,
+    &JUMP_BACKWARD_LONG,
+    &NEW_ARRAY_INSTRUCTION,
+    &INDEX_GET_INSTRUCTION,
+    &INDEX_SET_INSTRUCTION,
+    &ARRAY_PUSH_INSTRUCTION,
+    &LEN_INSTRUCTION,
+    &NEW_MAP_INSTRUCTION,
+    &MAP_INSERT_INSTRUCTION,
+    &MAP_GET_INSTRUCTION,
+    &MAP_REMOVE_INSTRUCTION,
+    &MAP_CONTAINS_INSTRUCTION,
+    &MAP_KEYS_INSTRUCTION,
+    &CLOSURE_INSTRUCTION,
+    &GET_UPVALUE_INSTRUCTION,
+    &SET_UPVALUE_INSTRUCTION,
+    &CLOSE_UPVALUE_INSTRUCTION,
+    &CLASS_INSTRUCTION,
+    &METHOD_INSTRUCTION,
+    &INVOKE_INSTRUCTION,
+    &CLASS_LONG_INSTRUCTION,
+    &METHOD_LONG_INSTRUCTION,
+    &INVOKE_LONG_INSTRUCTION,
+    &SET_PROTOTYPE_INSTRUCTION,
+    &GET_PROTOTYPE_INSTRUCTION,
+    &MODULO_INSTRUCTION,
+    &BIT_AND_INSTRUCTION,
+    &BIT_OR_INSTRUCTION,
+    &BIT_XOR_INSTRUCTION,
+    &BIT_NOT_INSTRUCTION,
+    &SHIFT_LEFT_INSTRUCTION,
+    &SHIFT_RIGHT_INSTRUCTION,
+    &GREATER_EQUAL_INSTRUCTION,
+    &LESS_EQUAL_INSTRUCTION,
+    &NOT_EQUAL_INSTRUCTION,
+    &TO_JSON_INSTRUCTION,
+    &FROM_JSON_INSTRUCTION,
+    &HAS_FIELD_INSTRUCTION,
+    &DELETE_FIELD_INSTRUCTION,
+    &GET_FIELD_OR_NULL_INSTRUCTION,
+    &GET_FIELD_DYNAMIC_INSTRUCTION,
+    &SET_FIELD_DYNAMIC_INSTRUCTION,
+    &HAS_FIELD_LONG_INSTRUCTION,
+    &DELETE_FIELD_LONG_INSTRUCTION,
+    &GET_FIELD_OR_NULL_LONG_INSTRUCTION,
+    &TYPE_OF_INSTRUCTION,
+    &IS_TYPE_INSTRUCTION,
+    &IS_TYPE_LONG_INSTRUCTION,
+];
+
+const WRAPPING_ARITHMETIC_INSTRUCTIONS: [&JexInstruction; 5] = [
+    &WRAPPING_NEGATE_INSTRUCTION,
+    &WRAPPING_ADD_INSTRUCTION,
+    &WRAPPING_SUBTRACT_INSTRUCTION,
+    &WRAPPING_MULTIPLY_INSTRUCTION,
+    &WRAPPING_DIVIDE_INSTRUCTION,
 ];
 
+const SATURATING_ARITHMETIC_INSTRUCTIONS: [&JexInstruction; 5] = [
+    &SATURATING_NEGATE_INSTRUCTION,
+    &SATURATING_ADD_INSTRUCTION,
+    &SATURATING_SUBTRACT_INSTRUCTION,
+    &SATURATING_MULTIPLY_INSTRUCTION,
+    &SATURATING_DIVIDE_INSTRUCTION,
+];
+
+lazy_static! {
+    static ref WRAPPING_JEX_INSTRUCTIONS: Vec<&'static JexInstruction> =
+        replace_instructions(&WRAPPING_ARITHMETIC_INSTRUCTIONS);
+    static ref SATURATING_JEX_INSTRUCTIONS: Vec<&'static JexInstruction> =
+        replace_instructions(&SATURATING_ARITHMETIC_INSTRUCTIONS);
+}
+
+/// Returns `JEX_INSTRUCTIONS` where the integer operators follow the given `ArithmeticMode`.
+pub fn jex_instructions(mode: ArithmeticMode) -> &'static [&'static JexInstruction] {
+    match mode {
+        ArithmeticMode::Checked => &JEX_INSTRUCTIONS,
+        ArithmeticMode::Wrapping => &WRAPPING_JEX_INSTRUCTIONS,
+        ArithmeticMode::Saturating => &SATURATING_JEX_INSTRUCTIONS,
+    }
+}
+
+/// Replaces instructions in `JEX_INSTRUCTIONS` with `replacements` that have the same op codes.
+fn replace_instructions(replacements: &[&'static JexInstruction]) -> Vec<&'static JexInstruction> {
+    JEX_INSTRUCTIONS
+        .iter()
+        .map(|instruction| {
+            replacements
+                .iter()
+                .find(|replacement| replacement.op_code == instruction.op_code)
+                .unwrap_or(instruction)
+        })
+        .copied()
+        .collect()
+}
+
 pub mod types {
     use extendable_vm::{Instruction, InstructionTable};
 
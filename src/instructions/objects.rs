@@ -1,16 +1,18 @@
 use crate::exceptions::runtime_exceptions::{
-    ExpectedInstructionArgument, FieldNotFound, NotObjectException, UnaryOperatorNotDefined,
+    ExpectedInstructionArgument, FieldNotFound, NotObjectException, TypeException,
 };
 use crate::instructions::op_codes::JexOpCode;
 use crate::instructions::types::JexInstruction;
 use crate::types::JexMachine;
-use crate::values::values::JexValue;
+use crate::values::get_type::GetType;
+use crate::values::values::{JexBoundMethod, JexFunction, JexValue};
 use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
+use std::rc::Rc;
 
 pub const NEW_INSTANCE_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::NewInstance as u8,
     name: "NEW_INSTANCE",
-    instruction_fn: InstructionFn::Const(|| JexValue::new_object()),
+    instruction_fn: InstructionFn::Const(JexValue::new_object),
 };
 
 pub const GET_FIELD_INSTRUCTION: JexInstruction = Instruction {
@@ -22,6 +24,15 @@ pub const GET_FIELD_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const GET_FIELD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetFieldLong as u8,
+    name: "GET_FIELD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: get_field_long_instruction,
+    },
+};
+
 pub const SET_FIELD_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::SetField as u8,
     name: "SET_FIELD",
@@ -31,44 +42,357 @@ pub const SET_FIELD_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const SET_FIELD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetFieldLong as u8,
+    name: "SET_FIELD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: set_field_long_instruction,
+    },
+};
+
+pub const HAS_FIELD_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::HasField as u8,
+    name: "HAS_FIELD",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: has_field_instruction,
+    },
+};
+
+pub const HAS_FIELD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::HasFieldLong as u8,
+    name: "HAS_FIELD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: has_field_long_instruction,
+    },
+};
+
+pub const DELETE_FIELD_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::DeleteField as u8,
+    name: "DELETE_FIELD",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: delete_field_instruction,
+    },
+};
+
+pub const DELETE_FIELD_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::DeleteFieldLong as u8,
+    name: "DELETE_FIELD_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: delete_field_long_instruction,
+    },
+};
+
+pub const GET_FIELD_OR_NULL_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetFieldOrNull as u8,
+    name: "GET_FIELD_OR_NULL",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: get_field_or_null_instruction,
+    },
+};
+
+pub const GET_FIELD_OR_NULL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetFieldOrNullLong as u8,
+    name: "GET_FIELD_OR_NULL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: get_field_or_null_long_instruction,
+    },
+};
+
+pub const GET_FIELD_DYNAMIC_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetFieldDynamic as u8,
+    name: "GET_FIELD_DYNAMIC",
+    instruction_fn: InstructionFn::BinaryOp(get_field_dynamic),
+};
+
+pub const SET_FIELD_DYNAMIC_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetFieldDynamic as u8,
+    name: "SET_FIELD_DYNAMIC",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: set_field_dynamic_instruction,
+    },
+};
+
+pub const SET_PROTOTYPE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetPrototype as u8,
+    name: "SET_PROTOTYPE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 0,
+        instruction_fn: set_prototype_instruction,
+    },
+};
+
+pub const GET_PROTOTYPE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetPrototype as u8,
+    name: "GET_PROTOTYPE",
+    instruction_fn: InstructionFn::UnaryOp(get_prototype),
+};
+
 fn get_field_instruction(
     machine: &mut JexMachine,
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     // read constant_id of field name
     let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    get_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn get_field_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    get_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn set_field_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    // read constant_id of field name
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    set_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn set_field_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    set_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn has_field_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    has_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn has_field_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    has_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn has_field(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<(), Exception> {
+    let field_name = field_name(machine, chunk_id, constant_id)?;
+    let receiver = machine.pop_operand()?;
+    let has_field = find_property(&receiver, &field_name)?.is_some();
+    machine.push_operand(JexValue::Bool(has_field));
+    Ok(())
+}
+
+fn delete_field_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    delete_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn delete_field_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    delete_field(machine, args.chunk_id, usize::from(constant_id))
+}
+
+/// Removes the own field of the object if it has one, the object stays on the stack
+fn delete_field(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<(), Exception> {
+    let field_name = field_name(machine, chunk_id, constant_id)?;
+    let receiver = machine.peek_operand()?;
+    let instance = receiver
+        .as_instance()
+        .ok_or_else(|| NotObjectException::new(receiver))?;
+    instance.remove_field(&field_name);
+    Ok(())
+}
+
+fn get_field_or_null_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    get_field_or_null(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn get_field_or_null_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    get_field_or_null(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn get_field_or_null(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<(), Exception> {
+    let field_name = field_name(machine, chunk_id, constant_id)?;
+    let receiver = machine.pop_operand()?;
+    let field = find_property(&receiver, &field_name)?.unwrap_or_else(JexValue::null);
+    machine.push_operand(field);
+    Ok(())
+}
+
+fn get_field_dynamic(receiver: JexValue, name: JexValue) -> Result<JexValue, Exception> {
+    get_property(&receiver, dynamic_field_name(&name)?)
+}
+
+/// Pops the value and the name of the field and sets the field, the object stays on the stack
+fn set_field_dynamic_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let new_value = machine.pop_operand()?;
+    let name = machine.pop_operand()?;
+    let field_name = dynamic_field_name(&name)?;
+    put_property(machine.peek_operand()?, field_name.clone(), new_value)
+}
+
+/// Returns the string constant that is the name of the field
+fn field_name(
+    machine: &JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<String, Exception> {
     let field_name = machine
         .code
-        .get_constant(args.chunk_id, usize::from(constant_id))?
+        .get_constant(chunk_id, constant_id)?
+        .as_string()?;
+    Ok(field_name)
+}
+
+fn dynamic_field_name(name: &JexValue) -> Result<&String, Exception> {
+    name.as_string().ok_or_else(|| {
+        Exception::from(TypeException(format!(
+            "Field name must be a string but was {}",
+            name.get_type()
+        )))
+    })
+}
+
+fn get_field(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    constant_id: usize,
+) -> Result<(), Exception> {
+    let field_name = machine
+        .code
+        .get_constant(chunk_id, constant_id)?
         .as_string()?;
     let receiver = machine.pop_operand()?;
-    if let JexValue::Instance(instance) = receiver {
-        let field = instance
-            .get_field(field_name.as_str())
-            .ok_or(FieldNotFound(field_name))?;
-        machine.push_operand(field);
-        Ok(())
-    } else {
-        Err(Exception::from(NotObjectException::new(&receiver)))
+    let field = get_property(&receiver, &field_name)?;
+    machine.push_operand(field);
+    Ok(())
+}
+
+/// Returns the field of the instance or of its prototypes or, if there is no such field, its method bound to it
+pub fn get_property(receiver: &JexValue, name: &str) -> Result<JexValue, Exception> {
+    find_property(receiver, name)?.ok_or_else(|| Exception::from(FieldNotFound(name.to_string())))
+}
+
+/// Same as `get_property` but returns `None` if neither the instance, its prototypes nor its class has the name
+fn find_property(receiver: &JexValue, name: &str) -> Result<Option<JexValue>, Exception> {
+    let instance = receiver
+        .as_instance()
+        .ok_or_else(|| NotObjectException::new(receiver))?;
+    if let Some(field) = instance.find_field(name)? {
+        return Ok(Some(field));
     }
+    let method = instance.class().and_then(|class| class.get_method(name));
+    Ok(method.map(|method| {
+        let bound = JexBoundMethod {
+            receiver: receiver.clone(),
+            method,
+        };
+        JexValue::Function(JexFunction::BoundMethod(Rc::new(bound)))
+    }))
 }
 
-fn set_field_instruction(
+fn set_field(
     machine: &mut JexMachine,
-    mut args: InstructionPointer,
+    chunk_id: usize,
+    constant_id: usize,
 ) -> Result<(), Exception> {
-    // read constant_id of field name
-    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
     let field_name = machine
         .code
-        .get_constant(args.chunk_id, usize::from(constant_id))?
+        .get_constant(chunk_id, constant_id)?
         .as_string()?;
     let new_value = machine.pop_operand()?;
-    let receiver = machine.peek_operand()?;
+    put_property(machine.peek_operand()?, field_name, new_value)
+}
+
+fn put_property(receiver: &JexValue, name: String, value: JexValue) -> Result<(), Exception> {
     if let JexValue::Instance(instance) = receiver {
-        instance.put_field(field_name, new_value);
+        instance.put_field(name, value);
         Ok(())
     } else {
         Err(Exception::from(NotObjectException::new(receiver)))
     }
 }
+
+/// Pops the prototype which is an object or null and sets it, the object stays on the stack
+fn set_prototype_instruction(
+    machine: &mut JexMachine,
+    _args: InstructionPointer,
+) -> Result<(), Exception> {
+    let prototype = match machine.pop_operand()? {
+        JexValue::Instance(prototype) => Some(prototype),
+        JexValue::Null(_) => None,
+        other => {
+            return Err(Exception::from(TypeException(format!(
+                "Prototype must be an object or null but was {}",
+                other.get_type()
+            ))))
+        }
+    };
+    let receiver = machine.peek_operand()?;
+    let instance = receiver
+        .as_instance()
+        .ok_or_else(|| NotObjectException::new(receiver))?;
+    instance.set_prototype(prototype);
+    Ok(())
+}
+
+/// Returns the prototype of the object or null if it does not have one
+fn get_prototype(receiver: JexValue) -> Result<JexValue, Exception> {
+    let instance = receiver
+        .as_instance()
+        .ok_or_else(|| NotObjectException::new(&receiver))?;
+    Ok(instance
+        .prototype()
+        .map(JexValue::Instance)
+        .unwrap_or_else(JexValue::null))
+}
@@ -1,4 +1,6 @@
-#[derive(Copy, Clone)]
+use std::convert::TryFrom;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum JexOpCode {
     Constant = 0,
     Null = 1,
@@ -31,4 +33,161 @@ pub enum JexOpCode {
     NewInstance = 28,
     GetField = 29,
     SetField = 30,
+    ParseFloat = 31,
+    ConstantLong = 32,
+    GetLocalLong = 33,
+    SetLocalLong = 34,
+    GetGlobalLong = 35,
+    DefineGlobalLong = 36,
+    SetGlobalLong = 37,
+    GetFieldLong = 38,
+    SetFieldLong = 39,
+    JumpForwardLong = 40,
+    JumpForwardIfFalseLong = 41,
+    JumpBackwardLong = 42,
+    NewArray = 43,
+    IndexGet = 44,
+    IndexSet = 45,
+    ArrayPush = 46,
+    Len = 47,
+    NewMap = 48,
+    MapInsert = 49,
+    MapGet = 50,
+    MapRemove = 51,
+    MapContains = 52,
+    MapKeys = 53,
+    Closure = 54,
+    GetUpvalue = 55,
+    SetUpvalue = 56,
+    CloseUpvalue = 57,
+    Class = 58,
+    Method = 59,
+    Invoke = 60,
+    ClassLong = 61,
+    MethodLong = 62,
+    InvokeLong = 63,
+    SetPrototype = 64,
+    GetPrototype = 65,
+    Modulo = 66,
+    BitAnd = 67,
+    BitOr = 68,
+    BitXor = 69,
+    BitNot = 70,
+    ShiftLeft = 71,
+    ShiftRight = 72,
+    GreaterEqual = 73,
+    LessEqual = 74,
+    NotEqual = 75,
+    ToJson = 76,
+    FromJson = 77,
+    HasField = 78,
+    DeleteField = 79,
+    GetFieldOrNull = 80,
+    GetFieldDynamic = 81,
+    SetFieldDynamic = 82,
+    HasFieldLong = 83,
+    DeleteFieldLong = 84,
+    GetFieldOrNullLong = 85,
+    TypeOf = 86,
+    IsType = 87,
+    IsTypeLong = 88,
+}
+
+impl TryFrom<u8> for JexOpCode {
+    type Error = u8;
+
+    fn try_from(op_code: u8) -> Result<Self, Self::Error> {
+        match op_code {
+            0 => Ok(JexOpCode::Constant),
+            1 => Ok(JexOpCode::Null),
+            2 => Ok(JexOpCode::True),
+            3 => Ok(JexOpCode::False),
+            4 => Ok(JexOpCode::Pop),
+            5 => Ok(JexOpCode::GetLocal),
+            6 => Ok(JexOpCode::SetLocal),
+            7 => Ok(JexOpCode::GetGlobal),
+            8 => Ok(JexOpCode::DefineGlobal),
+            9 => Ok(JexOpCode::SetGlobal),
+            10 => Ok(JexOpCode::Print),
+            11 => Ok(JexOpCode::Not),
+            12 => Ok(JexOpCode::Equal),
+            13 => Ok(JexOpCode::Greater),
+            14 => Ok(JexOpCode::Less),
+            15 => Ok(JexOpCode::Negate),
+            16 => Ok(JexOpCode::Add),
+            17 => Ok(JexOpCode::Subtract),
+            18 => Ok(JexOpCode::Multiply),
+            19 => Ok(JexOpCode::Divide),
+            20 => Ok(JexOpCode::JumpForward),
+            21 => Ok(JexOpCode::JumpForwardIfFalse),
+            22 => Ok(JexOpCode::JumpBackward),
+            23 => Ok(JexOpCode::Call),
+            24 => Ok(JexOpCode::Return),
+            25 => Ok(JexOpCode::ToString),
+            26 => Ok(JexOpCode::ReadLine),
+            27 => Ok(JexOpCode::ParseInt),
+            28 => Ok(JexOpCode::NewInstance),
+            29 => Ok(JexOpCode::GetField),
+            30 => Ok(JexOpCode::SetField),
+            31 => Ok(JexOpCode::ParseFloat),
+            32 => Ok(JexOpCode::ConstantLong),
+            33 => Ok(JexOpCode::GetLocalLong),
+            34 => Ok(JexOpCode::SetLocalLong),
+            35 => Ok(JexOpCode::GetGlobalLong),
+            36 => Ok(JexOpCode::DefineGlobalLong),
+            37 => Ok(JexOpCode::SetGlobalLong),
+            38 => Ok(JexOpCode::GetFieldLong),
+            39 => Ok(JexOpCode::SetFieldLong),
+            40 => Ok(JexOpCode::JumpForwardLong),
+            41 => Ok(JexOpCode::JumpForwardIfFalseLong),
+            42 => Ok(JexOpCode::JumpBackwardLong),
+            43 => Ok(JexOpCode::NewArray),
+            44 => Ok(JexOpCode::IndexGet),
+            45 => Ok(JexOpCode::IndexSet),
+            46 => Ok(JexOpCode::ArrayPush),
+            47 => Ok(JexOpCode::Len),
+            48 => Ok(JexOpCode::NewMap),
+            49 => Ok(JexOpCode::MapInsert),
+            50 => Ok(JexOpCode::MapGet),
+            51 => Ok(JexOpCode::MapRemove),
+            52 => Ok(JexOpCode::MapContains),
+            53 => Ok(JexOpCode::MapKeys),
+            54 => Ok(JexOpCode::Closure),
+            55 => Ok(JexOpCode::GetUpvalue),
+            56 => Ok(JexOpCode::SetUpvalue),
+            57 => Ok(JexOpCode::CloseUpvalue),
+            58 => Ok(JexOpCode::Class),
+            59 => Ok(JexOpCode::Method),
+            60 => Ok(JexOpCode::Invoke),
+            61 => Ok(JexOpCode::ClassLong),
+            62 => Ok(JexOpCode::MethodLong),
+            63 => Ok(JexOpCode::InvokeLong),
+            64 => Ok(JexOpCode::SetPrototype),
+            65 => Ok(JexOpCode::GetPrototype),
+            66 => Ok(JexOpCode::Modulo),
+            67 => Ok(JexOpCode::BitAnd),
+            68 => Ok(JexOpCode::BitOr),
+            69 => Ok(JexOpCode::BitXor),
+            70 => Ok(JexOpCode::BitNot),
+            71 => Ok(JexOpCode::ShiftLeft),
+            72 => Ok(JexOpCode::ShiftRight),
+            73 => Ok(JexOpCode::GreaterEqual),
+            74 => Ok(JexOpCode::LessEqual),
+            75 => Ok(JexOpCode::NotEqual),
+            76 => Ok(JexOpCode::ToJson),
+            77 => Ok(JexOpCode::FromJson),
+            78 => Ok(JexOpCode::HasField),
+            79 => Ok(JexOpCode::DeleteField),
+            80 => Ok(JexOpCode::GetFieldOrNull),
+            81 => Ok(JexOpCode::GetFieldDynamic),
+            82 => Ok(JexOpCode::SetFieldDynamic),
+            83 => Ok(JexOpCode::HasFieldLong),
+            84 => Ok(JexOpCode::DeleteFieldLong),
+            85 => Ok(JexOpCode::GetFieldOrNullLong),
+            86 => Ok(JexOpCode::TypeOf),
+            87 => Ok(JexOpCode::IsType),
+            88 => Ok(JexOpCode::IsTypeLong),
+            _ => Err(op_code),
+        }
+    }
 }
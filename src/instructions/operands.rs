@@ -0,0 +1,232 @@
+use crate::instructions::op_codes::JexOpCode;
+use std::convert::TryFrom;
+
+/// Describes what an instruction argument means
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OperandKind {
+    /// Index of a constant in the constant pool of the current chunk
+    Constant,
+    /// Index of a string constant that is used as a name of a global or a field
+    Identifier,
+    /// Offset of an operand from the start of the current call frame
+    LocalSlot,
+    /// Number of arguments that are passed to a function
+    Arity,
+    /// Number of values that are taken from the stack
+    Count,
+    /// Number of variables that a closure captures, the captures follow the operands
+    Captures,
+    /// Index of a variable that was captured by the current closure
+    Upvalue,
+    /// Number of bytes to jump forward by, counted from the end of the instruction
+    ForwardOffset,
+    /// Number of bytes to jump backward by, counted from the end of the instruction
+    BackwardOffset,
+}
+
+/// An instruction argument which is stored in `n_bytes` little-endian bytes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Operand {
+    pub kind: OperandKind,
+    pub n_bytes: usize,
+}
+
+impl Operand {
+    /// Reads the value of the operand from the beginning of `bytes`
+    pub fn read(&self, bytes: &[u8]) -> Option<usize> {
+        match self.n_bytes {
+            1 => bytes.first().map(|byte| usize::from(*byte)),
+            2 if bytes.len() >= 2 => Some(usize::from(u16::from_le_bytes([bytes[0], bytes[1]]))),
+            _ => None,
+        }
+    }
+    /// Encodes the operand, returns `None` if `value` does not fit into `n_bytes`
+    pub fn write(&self, value: usize) -> Option<Vec<u8>> {
+        match self.n_bytes {
+            1 if value <= usize::from(u8::MAX) => Some(vec![value as u8]),
+            2 if value <= usize::from(u16::MAX) => Some((value as u16).to_le_bytes().to_vec()),
+            _ => None,
+        }
+    }
+}
+
+/// A variable that `CLOSURE` captures.
+///
+/// Every capture is encoded in 2 bytes after the operands of the instruction:
+/// `1, slot` captures a local of the current call frame
+/// and `0, index` captures a variable that was captured by the current closure.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Capture {
+    Local(usize),
+    Upvalue(usize),
+}
+
+impl Capture {
+    pub const N_BYTES: usize = 2;
+
+    /// Reads a capture from the beginning of `bytes`
+    pub fn read(bytes: &[u8]) -> Option<Capture> {
+        match bytes {
+            [1, slot, ..] => Some(Capture::Local(usize::from(*slot))),
+            [0, index, ..] => Some(Capture::Upvalue(usize::from(*index))),
+            _ => None,
+        }
+    }
+    /// Encodes the capture, returns `None` if the index does not fit into a byte
+    pub fn write(&self) -> Option<Vec<u8>> {
+        let (kind, index) = match self {
+            Capture::Local(slot) => (1, *slot),
+            Capture::Upvalue(index) => (0, *index),
+        };
+        u8::try_from(index).ok().map(|index| vec![kind, index])
+    }
+}
+
+impl JexOpCode {
+    /// Returns the arguments of the instruction in the order they follow the op code
+    pub fn operands(self) -> &'static [Operand] {
+        use OperandKind::*;
+        match self {
+            JexOpCode::Constant => &[Operand {
+                kind: Constant,
+                n_bytes: 1,
+            }],
+            JexOpCode::ConstantLong => &[Operand {
+                kind: Constant,
+                n_bytes: 2,
+            }],
+            JexOpCode::GetLocal | JexOpCode::SetLocal => &[Operand {
+                kind: LocalSlot,
+                n_bytes: 1,
+            }],
+            JexOpCode::GetLocalLong | JexOpCode::SetLocalLong => &[Operand {
+                kind: LocalSlot,
+                n_bytes: 2,
+            }],
+            JexOpCode::GetGlobal
+            | JexOpCode::DefineGlobal
+            | JexOpCode::SetGlobal
+            | JexOpCode::GetField
+            | JexOpCode::SetField
+            | JexOpCode::HasField
+            | JexOpCode::DeleteField
+            | JexOpCode::GetFieldOrNull
+            | JexOpCode::IsType
+            | JexOpCode::Class
+            | JexOpCode::Method => &[Operand {
+                kind: Identifier,
+                n_bytes: 1,
+            }],
+            JexOpCode::GetGlobalLong
+            | JexOpCode::DefineGlobalLong
+            | JexOpCode::SetGlobalLong
+            | JexOpCode::GetFieldLong
+            | JexOpCode::SetFieldLong
+            | JexOpCode::ClassLong
+            | JexOpCode::MethodLong
+            | JexOpCode::HasFieldLong
+            | JexOpCode::DeleteFieldLong
+            | JexOpCode::GetFieldOrNullLong
+            | JexOpCode::IsTypeLong => &[Operand {
+                kind: Identifier,
+                n_bytes: 2,
+            }],
+            JexOpCode::JumpForward | JexOpCode::JumpForwardIfFalse => &[Operand {
+                kind: ForwardOffset,
+                n_bytes: 1,
+            }],
+            JexOpCode::JumpForwardLong | JexOpCode::JumpForwardIfFalseLong => &[Operand {
+                kind: ForwardOffset,
+                n_bytes: 2,
+            }],
+            JexOpCode::JumpBackward => &[Operand {
+                kind: BackwardOffset,
+                n_bytes: 1,
+            }],
+            JexOpCode::JumpBackwardLong => &[Operand {
+                kind: BackwardOffset,
+                n_bytes: 2,
+            }],
+            JexOpCode::Call => &[Operand {
+                kind: Arity,
+                n_bytes: 1,
+            }],
+            JexOpCode::Invoke => &[
+                Operand {
+                    kind: Identifier,
+                    n_bytes: 1,
+                },
+                Operand {
+                    kind: Arity,
+                    n_bytes: 1,
+                },
+            ],
+            JexOpCode::InvokeLong => &[
+                Operand {
+                    kind: Identifier,
+                    n_bytes: 2,
+                },
+                Operand {
+                    kind: Arity,
+                    n_bytes: 1,
+                },
+            ],
+            JexOpCode::NewArray => &[Operand {
+                kind: Count,
+                n_bytes: 1,
+            }],
+            JexOpCode::Closure => &[Operand {
+                kind: Captures,
+                n_bytes: 1,
+            }],
+            JexOpCode::GetUpvalue | JexOpCode::SetUpvalue => &[Operand {
+                kind: Upvalue,
+                n_bytes: 1,
+            }],
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::op_codes::JexOpCode;
+    use crate::instructions::operands::Capture;
+    use crate::instructions::JEX_INSTRUCTIONS;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn operands_should_match_byte_arity_of_every_instruction() {
+        for instruction in JEX_INSTRUCTIONS.iter() {
+            let op_code = JexOpCode::try_from(instruction.op_code).unwrap();
+            let n_bytes: usize = op_code.operands().iter().map(|op| op.n_bytes).sum();
+            assert_eq!(
+                instruction.instruction_fn.byte_arity(),
+                n_bytes,
+                "{}",
+                instruction.name
+            );
+        }
+    }
+
+    #[test]
+    fn captures_should_be_read_after_being_written() {
+        for capture in &[Capture::Local(3), Capture::Upvalue(255)] {
+            assert_eq!(Some(*capture), Capture::read(&capture.write().unwrap()));
+        }
+        assert_eq!(None, Capture::Local(256).write());
+        assert_eq!(None, Capture::read(&[2, 0]));
+    }
+
+    #[test]
+    fn every_op_code_should_have_an_instruction() {
+        for op_code in 0..=u8::MAX {
+            if let Ok(parsed) = JexOpCode::try_from(op_code) {
+                assert_eq!(op_code, parsed as u8);
+                assert!(JEX_INSTRUCTIONS
+                    .iter()
+                    .any(|instruction| instruction.op_code == op_code));
+            }
+        }
+    }
+}
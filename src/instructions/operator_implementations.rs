@@ -1,29 +1,141 @@
-use crate::exceptions::runtime_exceptions::{OperatorNotDefined, UnaryOperatorNotDefined};
+use crate::exceptions::runtime_exceptions::{
+    DivisionByZero, IntegerOverflow, NegativeShiftAmount, OperatorNotDefined,
+    UnaryOperatorNotDefined,
+};
 use crate::types::JexMachine;
 use crate::values::to_output_string::ToOutputString;
 use crate::values::values::{JexObject, JexValue};
 use extendable_vm::{Exception, InstructionPointer};
 use scanrs::scanln;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::rc::Rc;
+use std::str::FromStr;
+
+/// Defines what integer operators do when the result does not fit into an `i32`.
+///
+/// `Checked` raises an `IntegerOverflow` exception, `Wrapping` wraps around the boundary of the type
+/// and `Saturating` clamps the result to `i32::MIN` or `i32::MAX`.
+/// Division by zero raises `DivisionByZero` in every mode.
+/// Modulo, bitwise and shift operators never overflow, so they behave the same in every mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ArithmeticMode {
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
 
-pub fn plus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+impl FromStr for ArithmeticMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "checked" => Ok(ArithmeticMode::Checked),
+            "wrapping" => Ok(ArithmeticMode::Wrapping),
+            "saturating" => Ok(ArithmeticMode::Saturating),
+            _ => Err(format!("Unknown arithmetic mode {}", s)),
+        }
+    }
+}
+
+/// Promotes a pair of numeric operands to floats.
+///
+/// Returns `None` unless at least one operand is a float and the other one is an int or a float.
+/// Two ints are never promoted: they are handled by the integer versions of the operators.
+fn promote_to_floats(left: &JexValue, right: &JexValue) -> Option<(f64, f64)> {
+    match (left, right) {
+        (JexValue::Float(left), JexValue::Float(right)) => Some((*left, *right)),
+        (JexValue::Int(left), JexValue::Float(right)) => Some((f64::from(*left), *right)),
+        (JexValue::Float(left), JexValue::Int(right)) => Some((*left, f64::from(*right))),
+        _ => None,
+    }
+}
+
+fn overflowed(result: Option<i32>, operator_name: &'static str) -> Result<i32, Exception> {
+    result.ok_or_else(|| Exception::from(IntegerOverflow(operator_name)))
+}
+
+fn add_ints(mode: ArithmeticMode, left: i32, right: i32) -> Result<i32, Exception> {
+    match mode {
+        ArithmeticMode::Checked => overflowed(left.checked_add(right), "plus"),
+        ArithmeticMode::Wrapping => Ok(left.wrapping_add(right)),
+        ArithmeticMode::Saturating => Ok(left.saturating_add(right)),
+    }
+}
+
+fn subtract_ints(mode: ArithmeticMode, left: i32, right: i32) -> Result<i32, Exception> {
+    match mode {
+        ArithmeticMode::Checked => overflowed(left.checked_sub(right), "minus"),
+        ArithmeticMode::Wrapping => Ok(left.wrapping_sub(right)),
+        ArithmeticMode::Saturating => Ok(left.saturating_sub(right)),
+    }
+}
+
+fn multiply_ints(mode: ArithmeticMode, left: i32, right: i32) -> Result<i32, Exception> {
+    match mode {
+        ArithmeticMode::Checked => overflowed(left.checked_mul(right), "multiply"),
+        ArithmeticMode::Wrapping => Ok(left.wrapping_mul(right)),
+        ArithmeticMode::Saturating => Ok(left.saturating_mul(right)),
+    }
+}
+
+fn divide_ints(mode: ArithmeticMode, left: i32, right: i32) -> Result<i32, Exception> {
+    if right == 0 {
+        return Err(Exception::from(DivisionByZero));
+    }
+    // the only overflowing division is i32::MIN / -1
+    match mode {
+        ArithmeticMode::Checked => overflowed(left.checked_div(right), "divide"),
+        ArithmeticMode::Wrapping => Ok(left.wrapping_div(right)),
+        ArithmeticMode::Saturating => Ok(left.saturating_div(right)),
+    }
+}
+
+fn negate_int(mode: ArithmeticMode, int: i32) -> Result<i32, Exception> {
+    match mode {
+        ArithmeticMode::Checked => overflowed(int.checked_neg(), "negate"),
+        ArithmeticMode::Wrapping => Ok(int.wrapping_neg()),
+        ArithmeticMode::Saturating => Ok(int.saturating_neg()),
+    }
+}
+
+fn plus_in_mode(
+    mode: ArithmeticMode,
+    left: JexValue,
+    right: JexValue,
+) -> Result<JexValue, Exception> {
     match (left, right) {
-        (JexValue::Int(left), JexValue::Int(right)) => Ok(JexValue::Int(left + right)),
+        (JexValue::Int(left), JexValue::Int(right)) => {
+            Ok(JexValue::Int(add_ints(mode, left, right)?))
+        }
         (JexValue::Object(left), JexValue::Object(right)) => {
             let JexObject::String(left) = &*left;
             let JexObject::String(right) = &*right;
             let result = left.clone() + right;
             Ok(JexValue::Object(Rc::new(JexObject::String(result))))
         }
-        (left, right) => Err(Exception::from(OperatorNotDefined::new(
-            "plus", &left, &right,
-        ))),
+        (left, right) => {
+            if let Some((left, right)) = promote_to_floats(&left, &right) {
+                Ok(JexValue::Float(left + right))
+            } else {
+                Err(Exception::from(OperatorNotDefined::new(
+                    "plus", &left, &right,
+                )))
+            }
+        }
     }
 }
 
-pub fn minus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+fn minus_in_mode(
+    mode: ArithmeticMode,
+    left: JexValue,
+    right: JexValue,
+) -> Result<JexValue, Exception> {
     if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
-        Ok(JexValue::Int(left - right))
+        Ok(JexValue::Int(subtract_ints(mode, *left, *right)?))
+    } else if let Some((left, right)) = promote_to_floats(&left, &right) {
+        Ok(JexValue::Float(left - right))
     } else {
         Err(Exception::from(OperatorNotDefined::new(
             "minus", &left, &right,
@@ -31,9 +143,15 @@ pub fn minus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
     }
 }
 
-pub fn multiply(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+fn multiply_in_mode(
+    mode: ArithmeticMode,
+    left: JexValue,
+    right: JexValue,
+) -> Result<JexValue, Exception> {
     if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
-        Ok(JexValue::Int(left * right))
+        Ok(JexValue::Int(multiply_ints(mode, *left, *right)?))
+    } else if let Some((left, right)) = promote_to_floats(&left, &right) {
+        Ok(JexValue::Float(left * right))
     } else {
         Err(Exception::from(OperatorNotDefined::new(
             "multiply", &left, &right,
@@ -41,9 +159,15 @@ pub fn multiply(left: JexValue, right: JexValue) -> Result<JexValue, Exception>
     }
 }
 
-pub fn divide(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+fn divide_in_mode(
+    mode: ArithmeticMode,
+    left: JexValue,
+    right: JexValue,
+) -> Result<JexValue, Exception> {
     if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
-        Ok(JexValue::Int(left / right))
+        Ok(JexValue::Int(divide_ints(mode, *left, *right)?))
+    } else if let Some((left, right)) = promote_to_floats(&left, &right) {
+        Ok(JexValue::Float(left / right))
     } else {
         Err(Exception::from(OperatorNotDefined::new(
             "divide", &left, &right,
@@ -51,12 +175,166 @@ pub fn divide(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
     }
 }
 
+fn negate_in_mode(mode: ArithmeticMode, value: JexValue) -> Result<JexValue, Exception> {
+    match value {
+        JexValue::Int(int) => Ok(JexValue::Int(negate_int(mode, int)?)),
+        JexValue::Float(float) => Ok(JexValue::Float(-float)),
+        value => Err(Exception::from(UnaryOperatorNotDefined::new(
+            "negate", &value,
+        ))),
+    }
+}
+
+pub fn plus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    plus_in_mode(ArithmeticMode::Checked, left, right)
+}
+
+pub fn wrapping_plus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    plus_in_mode(ArithmeticMode::Wrapping, left, right)
+}
+
+pub fn saturating_plus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    plus_in_mode(ArithmeticMode::Saturating, left, right)
+}
+
+pub fn minus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    minus_in_mode(ArithmeticMode::Checked, left, right)
+}
+
+pub fn wrapping_minus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    minus_in_mode(ArithmeticMode::Wrapping, left, right)
+}
+
+pub fn saturating_minus(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    minus_in_mode(ArithmeticMode::Saturating, left, right)
+}
+
+pub fn multiply(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    multiply_in_mode(ArithmeticMode::Checked, left, right)
+}
+
+pub fn wrapping_multiply(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    multiply_in_mode(ArithmeticMode::Wrapping, left, right)
+}
+
+pub fn saturating_multiply(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    multiply_in_mode(ArithmeticMode::Saturating, left, right)
+}
+
+pub fn divide(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    divide_in_mode(ArithmeticMode::Checked, left, right)
+}
+
+pub fn wrapping_divide(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    divide_in_mode(ArithmeticMode::Wrapping, left, right)
+}
+
+pub fn saturating_divide(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    divide_in_mode(ArithmeticMode::Saturating, left, right)
+}
+
 pub fn negate(value: JexValue) -> Result<JexValue, Exception> {
+    negate_in_mode(ArithmeticMode::Checked, value)
+}
+
+pub fn wrapping_negate(value: JexValue) -> Result<JexValue, Exception> {
+    negate_in_mode(ArithmeticMode::Wrapping, value)
+}
+
+pub fn saturating_negate(value: JexValue) -> Result<JexValue, Exception> {
+    negate_in_mode(ArithmeticMode::Saturating, value)
+}
+
+/// Remainder of the division that has the sign of `left`, so `left == (left / right) * right + left % right`.
+///
+/// Raises `DivisionByZero` if both operands are ints and `right` is 0, `i32::MIN % -1` is 0.
+pub fn modulo(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
+        if *right == 0 {
+            return Err(Exception::from(DivisionByZero));
+        }
+        Ok(JexValue::Int(left.wrapping_rem(*right)))
+    } else if let Some((left, right)) = promote_to_floats(&left, &right) {
+        Ok(JexValue::Float(left % right))
+    } else {
+        Err(Exception::from(OperatorNotDefined::new(
+            "modulo", &left, &right,
+        )))
+    }
+}
+
+fn bitwise_ints(
+    operator_name: &'static str,
+    left: JexValue,
+    right: JexValue,
+    operator: fn(i32, i32) -> i32,
+) -> Result<JexValue, Exception> {
+    if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
+        Ok(JexValue::Int(operator(*left, *right)))
+    } else {
+        Err(Exception::from(OperatorNotDefined::new(
+            operator_name,
+            &left,
+            &right,
+        )))
+    }
+}
+
+pub fn bit_and(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    bitwise_ints("bit and", left, right, |left, right| left & right)
+}
+
+pub fn bit_or(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    bitwise_ints("bit or", left, right, |left, right| left | right)
+}
+
+pub fn bit_xor(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    bitwise_ints("bit xor", left, right, |left, right| left ^ right)
+}
+
+pub fn bit_not(value: JexValue) -> Result<JexValue, Exception> {
     if let JexValue::Int(int) = value {
-        Ok(JexValue::Int(-int))
+        Ok(JexValue::Int(!int))
     } else {
         Err(Exception::from(UnaryOperatorNotDefined::new(
-            "negate", &value,
+            "bit not", &value,
+        )))
+    }
+}
+
+fn shift_amount(amount: i32) -> Result<u32, Exception> {
+    u32::try_from(amount).map_err(|_| Exception::from(NegativeShiftAmount(amount)))
+}
+
+/// Shifts the bits of `left` to the left, bits shifted past the sign bit are discarded.
+///
+/// Shifting by 32 or more bits gives 0, shifting by a negative amount raises `NegativeShiftAmount`.
+pub fn shift_left(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
+        let amount = shift_amount(*right)?;
+        Ok(JexValue::Int(left.checked_shl(amount).unwrap_or(0)))
+    } else {
+        Err(Exception::from(OperatorNotDefined::new(
+            "shift left",
+            &left,
+            &right,
+        )))
+    }
+}
+
+/// Shifts the bits of `left` to the right and fills the vacated bits with the sign bit.
+///
+/// Shifting by 32 or more bits gives 0 for non-negative ints and -1 for negative ones,
+/// shifting by a negative amount raises `NegativeShiftAmount`.
+pub fn shift_right(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
+        let amount = shift_amount(*right)?;
+        Ok(JexValue::Int(left >> amount.min(31)))
+    } else {
+        Err(Exception::from(OperatorNotDefined::new(
+            "shift right",
+            &left,
+            &right,
         )))
     }
 }
@@ -77,24 +355,52 @@ pub fn equal(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
     Ok(JexValue::Bool(left == right))
 }
 
-pub fn greater(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+pub fn not_equal(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    Ok(JexValue::Bool(left != right))
+}
+
+/// Compares numbers or strings and checks the ordering with `predicate`.
+///
+/// Strings are compared lexicographically by Unicode scalar values,
+/// comparisons with `NaN` are always false.
+fn compare(
+    operator_name: &'static str,
+    left: JexValue,
+    right: JexValue,
+    predicate: fn(Ordering) -> bool,
+) -> Result<JexValue, Exception> {
     if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
-        Ok(JexValue::Bool(left > right))
+        Ok(JexValue::Bool(predicate(left.cmp(right))))
+    } else if let (Some(left), Some(right)) = (left.as_string(), right.as_string()) {
+        // the byte order of UTF-8 is the order of Unicode scalar values
+        Ok(JexValue::Bool(predicate(left.cmp(right))))
+    } else if let Some((left, right)) = promote_to_floats(&left, &right) {
+        Ok(JexValue::Bool(
+            left.partial_cmp(&right).is_some_and(predicate),
+        ))
     } else {
         Err(Exception::from(OperatorNotDefined::new(
-            "greater", &left, &right,
+            operator_name,
+            &left,
+            &right,
         )))
     }
 }
 
+pub fn greater(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    compare("greater", left, right, Ordering::is_gt)
+}
+
+pub fn greater_equal(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    compare("greater equal", left, right, Ordering::is_ge)
+}
+
 pub fn less(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
-    if let (JexValue::Int(left), JexValue::Int(right)) = (&left, &right) {
-        Ok(JexValue::Bool(left < right))
-    } else {
-        Err(Exception::from(OperatorNotDefined::new(
-            "less", &left, &right,
-        )))
-    }
+    compare("less", left, right, Ordering::is_lt)
+}
+
+pub fn less_equal(left: JexValue, right: JexValue) -> Result<JexValue, Exception> {
+    compare("less equal", left, right, Ordering::is_le)
 }
 
 pub fn print(value: JexValue) -> Result<JexValue, Exception> {
@@ -114,7 +420,7 @@ pub fn parse_int(value: JexValue) -> Result<JexValue, Exception> {
     if let Some(str) = value.as_string() {
         Ok(str
             .parse::<i32>()
-            .map_or_else(|_e| JexValue::null(), |int| JexValue::Int(int)))
+            .map_or_else(|_e| JexValue::null(), JexValue::Int))
     } else {
         Err(Exception::from(UnaryOperatorNotDefined::new(
             "PARSE_INT",
@@ -123,12 +429,29 @@ pub fn parse_int(value: JexValue) -> Result<JexValue, Exception> {
     }
 }
 
+pub fn parse_float(value: JexValue) -> Result<JexValue, Exception> {
+    if let Some(str) = value.as_string() {
+        Ok(str
+            .parse::<f64>()
+            .map_or_else(|_e| JexValue::null(), JexValue::Float))
+    } else {
+        Err(Exception::from(UnaryOperatorNotDefined::new(
+            "PARSE_FLOAT",
+            &value,
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // PLUS
 
     use crate::instructions::operator_implementations::{
-        divide, equal, greater, less, minus, multiply, negate, not, parse_int, plus, to_string,
+        bit_and, bit_not, bit_or, bit_xor, divide, equal, greater, greater_equal, less, less_equal,
+        minus, modulo, multiply, negate, not, not_equal, parse_float, parse_int, plus,
+        saturating_divide, saturating_minus, saturating_multiply, saturating_negate,
+        saturating_plus, shift_left, shift_right, to_string, wrapping_divide, wrapping_minus,
+        wrapping_multiply, wrapping_negate, wrapping_plus,
     };
     use crate::values::values::JexValue;
 
@@ -149,6 +472,57 @@ mod tests {
         assert_eq!(expected, plus(left, right).unwrap());
     }
 
+    #[test]
+    fn plus_should_add_two_floats() {
+        assert_eq!(
+            JexValue::Float(0.75),
+            plus(JexValue::Float(0.5), JexValue::Float(0.25)).unwrap()
+        );
+    }
+
+    #[test]
+    fn plus_should_promote_int_to_float() {
+        assert_eq!(
+            JexValue::Float(3.5),
+            plus(JexValue::Int(3), JexValue::Float(0.5)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Float(3.5),
+            plus(JexValue::Float(0.5), JexValue::Int(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn plus_should_not_add_float_and_string() {
+        assert!(plus(JexValue::Float(1.0), JexValue::from_string("a".to_string())).is_err());
+    }
+
+    #[test]
+    fn plus_should_fail_on_overflow() {
+        assert_eq!(
+            "IntegerOverflow",
+            plus(JexValue::Int(i32::MAX), JexValue::Int(1))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn wrapping_plus_should_wrap_on_overflow() {
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            wrapping_plus(JexValue::Int(i32::MAX), JexValue::Int(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn saturating_plus_should_saturate_on_overflow() {
+        assert_eq!(
+            JexValue::Int(i32::MAX),
+            saturating_plus(JexValue::Int(i32::MAX), JexValue::Int(1)).unwrap()
+        );
+    }
+
     #[test]
     fn plus_should_not_add_two_bools() {
         assert!(plus(JexValue::Bool(true), JexValue::Bool(false)).is_err());
@@ -169,6 +543,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn minus_should_subtract_int_and_float() {
+        assert_eq!(
+            JexValue::Float(-0.5),
+            minus(JexValue::Int(1), JexValue::Float(1.5)).unwrap()
+        );
+    }
+
+    #[test]
+    fn minus_should_fail_on_overflow() {
+        assert_eq!(
+            "IntegerOverflow",
+            minus(JexValue::Int(i32::MIN), JexValue::Int(1))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn wrapping_and_saturating_minus_should_not_fail_on_overflow() {
+        assert_eq!(
+            JexValue::Int(i32::MAX),
+            wrapping_minus(JexValue::Int(i32::MIN), JexValue::Int(1)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            saturating_minus(JexValue::Int(i32::MIN), JexValue::Int(1)).unwrap()
+        );
+    }
+
     #[test]
     fn minus_should_not_subtract_two_bools() {
         assert!(minus(JexValue::Bool(true), JexValue::Bool(false)).is_err());
@@ -189,6 +593,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multiply_should_multiply_float_and_int() {
+        assert_eq!(
+            JexValue::Float(5.0),
+            multiply(JexValue::Float(2.5), JexValue::Int(2)).unwrap()
+        );
+    }
+
+    #[test]
+    fn multiply_should_fail_on_overflow() {
+        assert_eq!(
+            "IntegerOverflow",
+            multiply(JexValue::Int(i32::MAX), JexValue::Int(2))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn wrapping_and_saturating_multiply_should_not_fail_on_overflow() {
+        assert_eq!(
+            JexValue::Int(-2),
+            wrapping_multiply(JexValue::Int(i32::MAX), JexValue::Int(2)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            saturating_multiply(JexValue::Int(i32::MAX), JexValue::Int(-2)).unwrap()
+        );
+    }
+
     #[test]
     fn multiply_should_not_mul_two_bools() {
         assert!(multiply(JexValue::Bool(true), JexValue::Bool(false)).is_err());
@@ -217,6 +651,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn divide_should_not_floor_if_one_operand_is_float() {
+        assert_eq!(
+            JexValue::Float(2.5),
+            divide(JexValue::Int(5), JexValue::Float(2.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn divide_should_divide_float_by_zero_to_infinity() {
+        assert_eq!(
+            JexValue::Float(f64::INFINITY),
+            divide(JexValue::Float(1.0), JexValue::Int(0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn divide_should_fail_if_divided_by_zero() {
+        assert_eq!(
+            "DivisionByZero",
+            divide(JexValue::Int(1), JexValue::Int(0)).unwrap_err().name
+        );
+        assert_eq!(
+            "DivisionByZero",
+            wrapping_divide(JexValue::Int(1), JexValue::Int(0))
+                .unwrap_err()
+                .name
+        );
+        assert_eq!(
+            "DivisionByZero",
+            saturating_divide(JexValue::Int(1), JexValue::Int(0))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn divide_should_fail_if_min_int_is_divided_by_minus_1() {
+        assert_eq!(
+            "IntegerOverflow",
+            divide(JexValue::Int(i32::MIN), JexValue::Int(-1))
+                .unwrap_err()
+                .name
+        );
+    }
+
+    #[test]
+    fn wrapping_and_saturating_divide_should_not_fail_on_overflow() {
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            wrapping_divide(JexValue::Int(i32::MIN), JexValue::Int(-1)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(i32::MAX),
+            saturating_divide(JexValue::Int(i32::MIN), JexValue::Int(-1)).unwrap()
+        );
+    }
+
     #[test]
     fn divide_should_not_divide_two_bools() {
         assert!(divide(JexValue::Bool(true), JexValue::Bool(false)).is_err());
@@ -227,6 +719,139 @@ mod tests {
         assert!(divide(JexValue::Int(1), JexValue::null()).is_err());
     }
 
+    // MODULO
+
+    #[test]
+    fn modulo_should_keep_sign_of_dividend() {
+        assert_eq!(
+            JexValue::Int(1),
+            modulo(JexValue::Int(7), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(-1),
+            modulo(JexValue::Int(-7), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(1),
+            modulo(JexValue::Int(7), JexValue::Int(-3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn modulo_should_fail_if_divided_by_zero() {
+        assert_eq!(
+            "DivisionByZero",
+            modulo(JexValue::Int(1), JexValue::Int(0)).unwrap_err().name
+        );
+    }
+
+    #[test]
+    fn modulo_of_min_int_by_minus_1_should_be_0() {
+        assert_eq!(
+            JexValue::Int(0),
+            modulo(JexValue::Int(i32::MIN), JexValue::Int(-1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn modulo_should_work_for_floats() {
+        assert_eq!(
+            JexValue::Float(1.5),
+            modulo(JexValue::Float(7.5), JexValue::Int(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn modulo_should_not_work_for_bools() {
+        assert!(modulo(JexValue::Bool(true), JexValue::Int(1)).is_err());
+    }
+
+    // BITWISE
+
+    #[test]
+    fn bitwise_operators_should_work_on_twos_complement() {
+        assert_eq!(
+            JexValue::Int(0b1000),
+            bit_and(JexValue::Int(0b1100), JexValue::Int(0b1010)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(0b1110),
+            bit_or(JexValue::Int(0b1100), JexValue::Int(0b1010)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(0b0110),
+            bit_xor(JexValue::Int(0b1100), JexValue::Int(0b1010)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(6),
+            bit_and(JexValue::Int(-2), JexValue::Int(7)).unwrap()
+        );
+        assert_eq!(JexValue::Int(-1), bit_not(JexValue::Int(0)).unwrap());
+        assert_eq!(
+            JexValue::Int(i32::MAX),
+            bit_not(JexValue::Int(i32::MIN)).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitwise_operators_should_not_work_for_floats_and_bools() {
+        assert!(bit_and(JexValue::Float(1.0), JexValue::Int(1)).is_err());
+        assert!(bit_or(JexValue::Bool(true), JexValue::Bool(false)).is_err());
+        assert!(bit_not(JexValue::Float(1.0)).is_err());
+    }
+
+    // SHIFTS
+
+    #[test]
+    fn shift_left_should_discard_high_bits() {
+        assert_eq!(
+            JexValue::Int(40),
+            shift_left(JexValue::Int(5), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            shift_left(JexValue::Int(1), JexValue::Int(31)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(-4),
+            shift_left(JexValue::Int(-1), JexValue::Int(2)).unwrap()
+        );
+    }
+
+    #[test]
+    fn shift_right_should_keep_sign() {
+        assert_eq!(
+            JexValue::Int(5),
+            shift_right(JexValue::Int(40), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(-2),
+            shift_right(JexValue::Int(-8), JexValue::Int(2)).unwrap()
+        );
+    }
+
+    #[test]
+    fn shifts_by_32_or_more_bits_should_shift_out_all_bits() {
+        assert_eq!(
+            JexValue::Int(0),
+            shift_left(JexValue::Int(-1), JexValue::Int(32)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(0),
+            shift_right(JexValue::Int(i32::MAX), JexValue::Int(100)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(-1),
+            shift_right(JexValue::Int(-5), JexValue::Int(32)).unwrap()
+        );
+    }
+
+    #[test]
+    fn shifts_should_fail_on_negative_amount() {
+        assert!(shift_left(JexValue::Int(1), JexValue::Int(-1)).is_err());
+        assert!(shift_right(JexValue::Int(1), JexValue::Int(-1)).is_err());
+    }
+
     // NEGATE
 
     #[test]
@@ -234,6 +859,31 @@ mod tests {
         assert_eq!(JexValue::Int(-33), negate(JexValue::Int(33)).unwrap());
     }
 
+    #[test]
+    fn negate_should_negate_float() {
+        assert_eq!(JexValue::Float(-1.5), negate(JexValue::Float(1.5)).unwrap());
+    }
+
+    #[test]
+    fn negate_should_fail_on_min_int() {
+        assert_eq!(
+            "IntegerOverflow",
+            negate(JexValue::Int(i32::MIN)).unwrap_err().name
+        );
+    }
+
+    #[test]
+    fn wrapping_and_saturating_negate_should_not_fail_on_min_int() {
+        assert_eq!(
+            JexValue::Int(i32::MIN),
+            wrapping_negate(JexValue::Int(i32::MIN)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Int(i32::MAX),
+            saturating_negate(JexValue::Int(i32::MIN)).unwrap()
+        );
+    }
+
     #[test]
     fn negate_should_not_work_for_bool() {
         assert!(negate(JexValue::Bool(true)).is_err());
@@ -262,6 +912,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_string_should_keep_fractional_part_of_integral_float() {
+        assert_eq!(
+            JexValue::from_string(String::from("2.0")),
+            to_string(JexValue::Float(2.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_string_should_convert_float_to_string() {
+        assert_eq!(
+            JexValue::from_string(String::from("-0.125")),
+            to_string(JexValue::Float(-0.125)).unwrap()
+        );
+    }
+
     // NOT
 
     #[test]
@@ -388,6 +1054,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn greater_should_compare_int_and_float() {
+        assert_eq!(
+            JexValue::Bool(true),
+            greater(JexValue::Float(2.5), JexValue::Int(2)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            greater(JexValue::Int(2), JexValue::Float(2.5)).unwrap()
+        );
+    }
+
     #[test]
     fn greater_should_not_compare_bools() {
         assert!(greater(JexValue::Bool(true), JexValue::Bool(false)).is_err());
@@ -419,11 +1097,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn less_should_compare_floats() {
+        assert_eq!(
+            JexValue::Bool(true),
+            less(JexValue::Float(-0.5), JexValue::Float(0.5)).unwrap()
+        );
+    }
+
     #[test]
     fn less_should_not_compare_bools() {
         assert!(less(JexValue::Bool(true), JexValue::Bool(false)).is_err());
     }
 
+    // STRING COMPARISON
+
+    fn string(string: &str) -> JexValue {
+        JexValue::from_string(string.to_string())
+    }
+
+    #[test]
+    fn strings_should_be_compared_lexicographically() {
+        assert_eq!(
+            JexValue::Bool(true),
+            less(string("apple"), string("banana")).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(true),
+            less(string("app"), string("apple")).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            greater(string("Zoe"), string("adam")).unwrap()
+        );
+        assert_eq!(JexValue::Bool(false), less(string(""), string("")).unwrap());
+    }
+
+    #[test]
+    fn strings_should_be_compared_by_unicode_scalar_values() {
+        assert_eq!(
+            JexValue::Bool(true),
+            greater(string("é"), string("z")).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(true),
+            less(string("\u{FFFF}"), string("\u{1F600}")).unwrap()
+        );
+    }
+
+    #[test]
+    fn strings_should_not_be_compared_with_ints() {
+        assert!(less(string("1"), JexValue::Int(2)).is_err());
+        assert!(greater_equal(JexValue::Int(2), string("1")).is_err());
+    }
+
+    // GREATER EQUAL, LESS EQUAL
+
+    #[test]
+    fn greater_equal_and_less_equal_should_accept_equal_operands() {
+        for (left, right) in [
+            (JexValue::Int(3), JexValue::Int(3)),
+            (JexValue::Int(3), JexValue::Float(3.0)),
+            (string("abc"), string("abc")),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                JexValue::Bool(true),
+                greater_equal(left.clone(), right.clone()).unwrap()
+            );
+            assert_eq!(
+                JexValue::Bool(true),
+                less_equal(left.clone(), right.clone()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn greater_equal_and_less_equal_should_compare_different_operands() {
+        assert_eq!(
+            JexValue::Bool(true),
+            greater_equal(JexValue::Int(4), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            less_equal(JexValue::Int(4), JexValue::Int(3)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(true),
+            less_equal(string("a"), string("b")).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            greater_equal(JexValue::Float(-0.5), JexValue::Int(0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn comparisons_with_nan_should_be_false() {
+        let nan = || JexValue::Float(f64::NAN);
+        assert_eq!(JexValue::Bool(false), less_equal(nan(), nan()).unwrap());
+        assert_eq!(
+            JexValue::Bool(false),
+            greater_equal(nan(), JexValue::Int(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn greater_equal_and_less_equal_should_not_compare_bools() {
+        assert!(greater_equal(JexValue::Bool(true), JexValue::Bool(true)).is_err());
+        assert!(less_equal(JexValue::null(), JexValue::null()).is_err());
+    }
+
+    // NOT EQUAL
+
+    #[test]
+    fn not_equal_should_be_negation_of_equal() {
+        assert_eq!(
+            JexValue::Bool(true),
+            not_equal(JexValue::Int(1), JexValue::Int(2)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            not_equal(string("a"), string("a")).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(true),
+            not_equal(JexValue::null(), JexValue::Bool(false)).unwrap()
+        );
+        assert_eq!(
+            JexValue::Bool(false),
+            not_equal(JexValue::Int(1), JexValue::Float(1.0)).unwrap()
+        );
+    }
+
     // PARSE INT
 
     #[test]
@@ -460,4 +1267,20 @@ mod tests {
         let actual = parse_int(string).unwrap();
         assert_eq!(actual, JexValue::null());
     }
+
+    // PARSE FLOAT
+
+    #[test]
+    fn parse_float_should_parse_negative_fraction() {
+        let string = JexValue::from_string("-2.75".to_string());
+        let actual = parse_float(string).unwrap();
+        assert_eq!(actual, JexValue::Float(-2.75));
+    }
+
+    #[test]
+    fn parse_float_should_return_null_if_given_abc() {
+        let string = JexValue::from_string("abc".to_string());
+        let actual = parse_float(string).unwrap();
+        assert_eq!(actual, JexValue::null());
+    }
 }
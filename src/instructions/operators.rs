@@ -1,7 +1,10 @@
 use crate::instructions::op_codes::JexOpCode;
 use crate::instructions::operator_implementations::{
-    divide, equal, greater, less, minus, multiply, negate, not, parse_int, plus, print, read_line,
-    to_string,
+    bit_and, bit_not, bit_or, bit_xor, divide, equal, greater, greater_equal, less, less_equal,
+    minus, modulo, multiply, negate, not, not_equal, parse_float, parse_int, plus, print,
+    read_line, saturating_divide, saturating_minus, saturating_multiply, saturating_negate,
+    saturating_plus, shift_left, shift_right, to_string, wrapping_divide, wrapping_minus,
+    wrapping_multiply, wrapping_negate, wrapping_plus,
 };
 use crate::instructions::types::JexInstruction;
 use crate::values::values::JexValue;
@@ -37,6 +40,108 @@ pub const DIVIDE_INSTRUCTION: JexInstruction = Instruction {
     instruction_fn: InstructionFn::BinaryOp(divide),
 };
 
+pub const MODULO_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Modulo as u8,
+    name: "MODULO",
+    instruction_fn: InstructionFn::BinaryOp(modulo),
+};
+
+pub const BIT_AND_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::BitAnd as u8,
+    name: "BIT_AND",
+    instruction_fn: InstructionFn::BinaryOp(bit_and),
+};
+
+pub const BIT_OR_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::BitOr as u8,
+    name: "BIT_OR",
+    instruction_fn: InstructionFn::BinaryOp(bit_or),
+};
+
+pub const BIT_XOR_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::BitXor as u8,
+    name: "BIT_XOR",
+    instruction_fn: InstructionFn::BinaryOp(bit_xor),
+};
+
+pub const BIT_NOT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::BitNot as u8,
+    name: "BIT_NOT",
+    instruction_fn: InstructionFn::UnaryOp(bit_not),
+};
+
+pub const SHIFT_LEFT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ShiftLeft as u8,
+    name: "SHIFT_LEFT",
+    instruction_fn: InstructionFn::BinaryOp(shift_left),
+};
+
+pub const SHIFT_RIGHT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ShiftRight as u8,
+    name: "SHIFT_RIGHT",
+    instruction_fn: InstructionFn::BinaryOp(shift_right),
+};
+
+pub const WRAPPING_NEGATE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Negate as u8,
+    name: "NEGATE",
+    instruction_fn: InstructionFn::UnaryOp(wrapping_negate),
+};
+
+pub const WRAPPING_ADD_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Add as u8,
+    name: "ADD",
+    instruction_fn: InstructionFn::BinaryOp(wrapping_plus),
+};
+
+pub const WRAPPING_SUBTRACT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Subtract as u8,
+    name: "SUBTRACT",
+    instruction_fn: InstructionFn::BinaryOp(wrapping_minus),
+};
+
+pub const WRAPPING_MULTIPLY_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Multiply as u8,
+    name: "MULTIPLY",
+    instruction_fn: InstructionFn::BinaryOp(wrapping_multiply),
+};
+
+pub const WRAPPING_DIVIDE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Divide as u8,
+    name: "DIVIDE",
+    instruction_fn: InstructionFn::BinaryOp(wrapping_divide),
+};
+
+pub const SATURATING_NEGATE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Negate as u8,
+    name: "NEGATE",
+    instruction_fn: InstructionFn::UnaryOp(saturating_negate),
+};
+
+pub const SATURATING_ADD_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Add as u8,
+    name: "ADD",
+    instruction_fn: InstructionFn::BinaryOp(saturating_plus),
+};
+
+pub const SATURATING_SUBTRACT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Subtract as u8,
+    name: "SUBTRACT",
+    instruction_fn: InstructionFn::BinaryOp(saturating_minus),
+};
+
+pub const SATURATING_MULTIPLY_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Multiply as u8,
+    name: "MULTIPLY",
+    instruction_fn: InstructionFn::BinaryOp(saturating_multiply),
+};
+
+pub const SATURATING_DIVIDE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::Divide as u8,
+    name: "DIVIDE",
+    instruction_fn: InstructionFn::BinaryOp(saturating_divide),
+};
+
 pub const EQUAL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::Equal as u8,
     name: "EQUAL",
@@ -55,6 +160,24 @@ pub const LESS_INSTRUCTION: JexInstruction = Instruction {
     instruction_fn: InstructionFn::BinaryOp(less),
 };
 
+pub const GREATER_EQUAL_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GreaterEqual as u8,
+    name: "GREATER_EQUAL",
+    instruction_fn: InstructionFn::BinaryOp(greater_equal),
+};
+
+pub const LESS_EQUAL_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::LessEqual as u8,
+    name: "LESS_EQUAL",
+    instruction_fn: InstructionFn::BinaryOp(less_equal),
+};
+
+pub const NOT_EQUAL_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::NotEqual as u8,
+    name: "NOT_EQUAL",
+    instruction_fn: InstructionFn::BinaryOp(not_equal),
+};
+
 pub const NOT_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::Not as u8,
     name: "NOT",
@@ -88,6 +211,12 @@ pub const PARSE_INT_INSTRUCTION: JexInstruction = Instruction {
     instruction_fn: InstructionFn::UnaryOp(parse_int),
 };
 
+pub const PARSE_FLOAT_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::ParseFloat as u8,
+    name: "PARSE_FLOAT",
+    instruction_fn: InstructionFn::UnaryOp(parse_float),
+};
+
 pub const NULL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::Null as u8,
     name: "NULL",
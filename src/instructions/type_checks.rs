@@ -0,0 +1,65 @@
+use crate::exceptions::runtime_exceptions::ExpectedInstructionArgument;
+use crate::instructions::op_codes::JexOpCode;
+use crate::instructions::types::JexInstruction;
+use crate::types::JexMachine;
+use crate::values::get_type::GetType;
+use crate::values::values::JexValue;
+use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
+
+pub const TYPE_OF_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::TypeOf as u8,
+    name: "TYPE_OF",
+    instruction_fn: InstructionFn::UnaryOp(type_of),
+};
+
+pub const IS_TYPE_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::IsType as u8,
+    name: "IS_TYPE",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 1,
+        instruction_fn: is_type_instruction,
+    },
+};
+
+pub const IS_TYPE_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::IsTypeLong as u8,
+    name: "IS_TYPE_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: is_type_long_instruction,
+    },
+};
+
+/// Returns the name of the type that exceptions use, for example `Int` or `null`
+fn type_of(value: JexValue) -> Result<JexValue, Exception> {
+    Ok(JexValue::from_string(value.get_type()))
+}
+
+fn is_type_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    is_type(machine, args.chunk_id, usize::from(constant_id))
+}
+
+fn is_type_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let constant_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    is_type(machine, args.chunk_id, usize::from(constant_id))
+}
+
+/// Pops the value and checks if the name of its type is the string constant
+fn is_type(machine: &mut JexMachine, chunk_id: usize, constant_id: usize) -> Result<(), Exception> {
+    let expected_type = machine
+        .code
+        .get_constant(chunk_id, constant_id)?
+        .as_string()?;
+    let value = machine.pop_operand()?;
+    machine.push_operand(JexValue::Bool(value.get_type() == expected_type));
+    Ok(())
+}
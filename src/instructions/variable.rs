@@ -2,6 +2,7 @@ use crate::exceptions::runtime_exceptions::{ExpectedInstructionArgument, TypeExc
 use crate::instructions::op_codes::JexOpCode;
 use crate::instructions::types::JexInstruction;
 use crate::types::JexMachine;
+use crate::values::values::JexValue;
 use extendable_vm::{ByteReadable, Exception, Instruction, InstructionFn, InstructionPointer};
 
 pub const POP_INSTRUCTION: JexInstruction = Instruction {
@@ -22,6 +23,15 @@ pub const GET_LOCAL_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const GET_LOCAL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetLocalLong as u8,
+    name: "GET_LOCAL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: get_local_long_instruction,
+    },
+};
+
 pub const SET_LOCAL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::SetLocal as u8,
     name: "SET_LOCAL",
@@ -31,6 +41,15 @@ pub const SET_LOCAL_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const SET_LOCAL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetLocalLong as u8,
+    name: "SET_LOCAL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: set_local_long_instruction,
+    },
+};
+
 pub const GET_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::GetGlobal as u8,
     name: "GET_GLOBAL",
@@ -40,6 +59,15 @@ pub const GET_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const GET_GLOBAL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::GetGlobalLong as u8,
+    name: "GET_GLOBAL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: get_global_long_instruction,
+    },
+};
+
 pub const DEFINE_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::DefineGlobal as u8,
     name: "DEFINE_GLOBAL",
@@ -49,6 +77,15 @@ pub const DEFINE_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const DEFINE_GLOBAL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::DefineGlobalLong as u8,
+    name: "DEFINE_GLOBAL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: define_global_long_instruction,
+    },
+};
+
 pub const SET_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     op_code: JexOpCode::SetGlobal as u8,
     name: "SET_GLOBAL",
@@ -58,6 +95,15 @@ pub const SET_GLOBAL_INSTRUCTION: JexInstruction = Instruction {
     },
 };
 
+pub const SET_GLOBAL_LONG_INSTRUCTION: JexInstruction = Instruction {
+    op_code: JexOpCode::SetGlobalLong as u8,
+    name: "SET_GLOBAL_LONG",
+    instruction_fn: InstructionFn::Raw {
+        byte_arity: 2,
+        instruction_fn: set_global_long_instruction,
+    },
+};
+
 fn pop_instruction(
     machine: &mut JexMachine,
     mut _args: InstructionPointer,
@@ -71,11 +117,17 @@ fn get_local_instruction(
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let relative_slot = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    let last_frame_start = machine.peek_frame()?.start_slot;
-    let absolute_slot = last_frame_start + usize::from(relative_slot);
-    let value = machine.get_operand(absolute_slot)?.clone();
-    machine.push_operand(value);
-    Ok(())
+    get_local(machine, usize::from(relative_slot))
+}
+
+fn get_local_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let relative_slot = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    get_local(machine, usize::from(relative_slot))
 }
 
 fn set_local_instruction(
@@ -83,10 +135,17 @@ fn set_local_instruction(
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let relative_slot = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    let absolute_slot = machine.peek_frame()?.start_slot + usize::from(relative_slot);
-    let value = machine.pop_operand()?;
-    machine.set_operand(absolute_slot, value)?;
-    Ok(())
+    set_local(machine, usize::from(relative_slot))
+}
+
+fn set_local_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let relative_slot = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    set_local(machine, usize::from(relative_slot))
 }
 
 fn get_global_instruction(
@@ -94,9 +153,77 @@ fn get_global_instruction(
     mut args: InstructionPointer,
 ) -> Result<(), Exception> {
     let identifier_const_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
-    let identifier = machine
-        .code
-        .get_constant(args.chunk_id, usize::from(identifier_const_id))?;
+    get_global(machine, args.chunk_id, usize::from(identifier_const_id))
+}
+
+fn get_global_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let identifier_const_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    get_global(machine, args.chunk_id, usize::from(identifier_const_id))
+}
+
+fn define_global_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let identifier_const_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
+    define_global(machine, args.chunk_id, usize::from(identifier_const_id))
+}
+
+fn define_global_long_instruction(
+    machine: &mut JexMachine,
+    mut args: InstructionPointer,
+) -> Result<(), Exception> {
+    let identifier_const_id = machine
+        .read_u16(&mut args)
+        .ok_or(ExpectedInstructionArgument)?;
+    define_global(machine, args.chunk_id, usize::from(identifier_const_id))
+}
+
+fn set_global_instruction(
+    machine: &mut JexMachine,
+    args: InstructionPointer,
+) -> Result<(), Exception> {
+    define_global_instruction(machine, args)
+}
+
+fn set_global_long_instruction(
+    machine: &mut JexMachine,
+    args: InstructionPointer,
+) -> Result<(), Exception> {
+    define_global_long_instruction(machine, args)
+}
+
+fn get_local(machine: &mut JexMachine, relative_slot: usize) -> Result<(), Exception> {
+    let last_frame_start = machine.peek_frame()?.start_slot;
+    let absolute_slot = last_frame_start + relative_slot;
+    let value = machine.get_operand(absolute_slot)?.dereference();
+    machine.push_operand(value);
+    Ok(())
+}
+
+fn set_local(machine: &mut JexMachine, relative_slot: usize) -> Result<(), Exception> {
+    let absolute_slot = machine.peek_frame()?.start_slot + relative_slot;
+    let value = machine.pop_operand()?;
+    if let JexValue::Cell(cell) = machine.get_operand(absolute_slot)? {
+        // the variable is shared with closures
+        cell.set(value);
+        return Ok(());
+    }
+    machine.set_operand(absolute_slot, value)?;
+    Ok(())
+}
+
+fn get_global(
+    machine: &mut JexMachine,
+    chunk_id: usize,
+    identifier_const_id: usize,
+) -> Result<(), Exception> {
+    let identifier = machine.code.get_constant(chunk_id, identifier_const_id)?;
     let identifier_string = identifier.as_string()?;
     let value = machine.globals.get(&identifier_string).cloned();
     if let Some(value) = value {
@@ -110,23 +237,16 @@ fn get_global_instruction(
     }
 }
 
-fn define_global_instruction(
+fn define_global(
     machine: &mut JexMachine,
-    mut args: InstructionPointer,
+    chunk_id: usize,
+    identifier_const_id: usize,
 ) -> Result<(), Exception> {
-    let identifier_const_id = machine.read(&mut args).ok_or(ExpectedInstructionArgument)?;
     let identifier = machine
         .code
-        .get_constant(args.chunk_id, usize::from(identifier_const_id))?
+        .get_constant(chunk_id, identifier_const_id)?
         .as_string()?;
     let value = machine.pop_operand()?;
     machine.globals.insert(identifier, value);
     Ok(())
 }
-
-fn set_global_instruction(
-    machine: &mut JexMachine,
-    args: InstructionPointer,
-) -> Result<(), Exception> {
-    define_global_instruction(machine, args)
-}
@@ -2,23 +2,58 @@ use extendable_vm::{Code, InstructionTable, Machine};
 
 use code::bytecode_constants::JexConstant;
 
-use crate::instructions::JEX_INSTRUCTIONS;
+use crate::instructions::jex_instructions;
+use crate::library::define_library;
+use crate::options::JexMachineOptions;
 use crate::types::JexMachine;
-use crate::values::values::{JexFunction, JexValue};
+use crate::values::values::{JexFunction, JexNativeFunction, JexValue, NativeArity};
+use extendable_vm::Exception;
+use std::rc::Rc;
 
 pub mod code;
 pub mod exceptions;
+pub mod heap;
 pub mod instructions;
+pub mod library;
+pub mod options;
+pub mod runtime;
 pub mod values;
 
-pub fn build_jex_machine(code: &Code<JexConstant>) -> JexMachine {
-    let instruction_table = InstructionTable::instructions(&JEX_INSTRUCTIONS);
+pub fn build_jex_machine(code: &Code<JexConstant>) -> JexMachine<'_> {
+    build_jex_machine_with_options(code, &JexMachineOptions::default())
+}
+
+pub fn build_jex_machine_with_options<'a>(
+    code: &'a Code<JexConstant>,
+    options: &JexMachineOptions,
+) -> JexMachine<'a> {
+    let instruction_table =
+        InstructionTable::instructions(jex_instructions(options.arithmetic_mode));
     let mut machine = Machine::new(code, instruction_table);
     machine.push_operand(JexValue::Function(JexFunction::Script));
     machine.push_frame(0, "<script>".to_string(), 0);
+    define_library(&mut machine);
     machine
 }
 
+/// Defines a global `name` that holds a native function, so bytecode can get the global and call it.
+///
+/// Natives should be defined after the machine is built and before it is started.
+pub fn define_native<F>(machine: &mut JexMachine, name: &str, arity: NativeArity, function: F)
+where
+    F: Fn(&mut JexMachine, Vec<JexValue>) -> Result<JexValue, Exception> + 'static,
+{
+    let native = JexNativeFunction {
+        name: name.to_string(),
+        arity,
+        function: Box::new(function),
+    };
+    machine.globals.insert(
+        name.to_string(),
+        JexValue::Function(JexFunction::Native(Rc::new(native))),
+    );
+}
+
 pub mod types {
     use extendable_vm::Machine;
 
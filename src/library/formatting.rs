@@ -0,0 +1,36 @@
+use crate::define_native;
+use crate::types::JexMachine;
+use crate::values::to_output_string::to_pretty_string;
+use crate::values::values::{JexValue, NativeArity};
+use extendable_vm::Exception;
+
+/// Defines the globals that format values as text
+pub fn define_formatting_library(machine: &mut JexMachine) {
+    define_native(
+        machine,
+        "to_pretty_string",
+        NativeArity::Fixed(1),
+        pretty_string,
+    );
+}
+
+/// `to_pretty_string(value)` formats arrays, maps and objects on multiple indented lines
+fn pretty_string(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    Ok(JexValue::from_string(to_pretty_string(&args[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::library::formatting::pretty_string;
+    use crate::library::testing::call_native;
+    use crate::values::values::JexValue;
+
+    #[test]
+    fn pretty_string_should_put_elements_on_separate_lines() {
+        let array = JexValue::from_elements(vec![JexValue::Int(1), JexValue::Int(2)]);
+        assert_eq!(
+            JexValue::from_string("[\n  1,\n  2\n]".to_string()),
+            call_native(pretty_string, vec![array]).unwrap()
+        );
+    }
+}
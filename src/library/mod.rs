@@ -0,0 +1,38 @@
+use crate::library::formatting::define_formatting_library;
+use crate::library::objects::define_object_library;
+use crate::library::strings::define_string_library;
+use crate::types::JexMachine;
+
+pub mod formatting;
+pub mod objects;
+pub mod strings;
+
+/// Defines the built-in globals that every machine has
+pub fn define_library(machine: &mut JexMachine) {
+    define_string_library(machine);
+    define_object_library(machine);
+    define_formatting_library(machine);
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use crate::build_jex_machine;
+    use crate::code::bytecode_constants::JexConstant;
+    use crate::types::JexMachine;
+    use crate::values::values::JexValue;
+    use extendable_vm::{Chunk, Code, Exception};
+
+    pub(crate) type Native = fn(&mut JexMachine, Vec<JexValue>) -> Result<JexValue, Exception>;
+
+    /// Calls the native function with a machine that has an empty script
+    pub(crate) fn call_native(native: Native, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+        let code = Code {
+            chunks: vec![Chunk::<JexConstant> {
+                constants: vec![],
+                code: vec![],
+            }],
+        };
+        let mut machine = build_jex_machine(&code);
+        native(&mut machine, args)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::define_native;
+use crate::exceptions::runtime_exceptions::TypeException;
+use crate::types::JexMachine;
+use crate::values::get_type::GetType;
+use crate::values::values::{JexInstance, JexValue, NativeArity};
+use extendable_vm::Exception;
+use std::convert::TryFrom;
+
+/// Defines the globals that inspect and copy the own fields of objects.
+///
+/// Fields are listed in the order they were first set, fields of prototypes are not included.
+pub fn define_object_library(machine: &mut JexMachine) {
+    define_native(machine, "field_names", NativeArity::Fixed(1), field_names);
+    define_native(machine, "field_count", NativeArity::Fixed(1), field_count);
+    define_native(machine, "copy_object", NativeArity::Fixed(1), copy_object);
+    define_native(machine, "merge_fields", NativeArity::Fixed(2), merge_fields);
+}
+
+fn object_argument<'a>(
+    function: &str,
+    args: &'a [JexValue],
+    index: usize,
+) -> Result<&'a JexInstance, Exception> {
+    args[index].as_instance().ok_or_else(|| {
+        Exception::from(TypeException(format!(
+            "Argument {} of {} must be object but was {}",
+            index,
+            function,
+            args[index].get_type()
+        )))
+    })
+}
+
+/// `field_names(object)` returns an array of the names of the fields
+fn field_names(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let names = object_argument("field_names", &args, 0)?
+        .field_names()
+        .into_iter()
+        .map(JexValue::from_string)
+        .collect();
+    Ok(JexValue::from_elements(names))
+}
+
+/// `field_count(object)` returns the number of fields
+fn field_count(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let count = object_argument("field_count", &args, 0)?.field_count();
+    i32::try_from(count)
+        .map(JexValue::Int)
+        .map_err(|_| Exception::from(TypeException("Object has too many fields".to_string())))
+}
+
+/// `copy_object(object)` returns a new object of the same class and with the same prototype and fields.
+///
+/// The copy is shallow, values of the fields are shared with the original.
+fn copy_object(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let original = object_argument("copy_object", &args, 0)?;
+    let copy = match original.class() {
+        Some(class) => JexValue::new_instance_of(class.clone()),
+        None => JexValue::new_object(),
+    };
+    if let Some(instance) = copy.as_instance() {
+        instance.set_prototype(original.prototype());
+        for (name, value) in original.fields() {
+            instance.put_field(name, value);
+        }
+    }
+    Ok(copy)
+}
+
+/// `merge_fields(target, source)` sets the fields of `source` in `target` and returns `target`
+fn merge_fields(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let target = object_argument("merge_fields", &args, 0)?;
+    let source = object_argument("merge_fields", &args, 1)?;
+    for (name, value) in source.fields() {
+        target.put_field(name, value);
+    }
+    Ok(args[0].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::library::objects::{copy_object, field_count, field_names, merge_fields};
+    use crate::library::testing::call_native;
+    use crate::values::to_output_string::ToOutputString;
+    use crate::values::values::JexValue;
+
+    fn object(fields: Vec<(&str, i32)>) -> JexValue {
+        let object = JexValue::new_object();
+        for (name, value) in fields {
+            object
+                .as_instance()
+                .unwrap()
+                .put_field(name.to_string(), JexValue::Int(value));
+        }
+        object
+    }
+
+    #[test]
+    fn field_names_should_be_in_insertion_order() {
+        let object = object(vec![("b", 1), ("a", 2), ("c", 3)]);
+        object.as_instance().unwrap().remove_field("a");
+        object
+            .as_instance()
+            .unwrap()
+            .put_field("a".to_string(), JexValue::Int(4));
+        let names = call_native(field_names, vec![object.clone()]).unwrap();
+        assert_eq!("[\"b\", \"c\", \"a\"]", names.to_output_string());
+        assert_eq!(
+            JexValue::Int(3),
+            call_native(field_count, vec![object]).unwrap()
+        );
+    }
+
+    #[test]
+    fn copy_should_not_share_fields_with_original() {
+        let original = object(vec![("x", 1)]);
+        let copy = call_native(copy_object, vec![original.clone()]).unwrap();
+        copy.as_instance()
+            .unwrap()
+            .put_field("x".to_string(), JexValue::Int(2));
+        assert_eq!("{x: 1}", original.to_output_string());
+        assert_eq!("{x: 2}", copy.to_output_string());
+    }
+
+    #[test]
+    fn merge_should_overwrite_fields_of_target() {
+        let target = object(vec![("x", 1), ("y", 2)]);
+        let source = object(vec![("y", 3), ("z", 4)]);
+        let result = call_native(merge_fields, vec![target.clone(), source]).unwrap();
+        assert_eq!(target, result);
+        assert_eq!("{x: 1, y: 3, z: 4}", target.to_output_string());
+    }
+
+    #[test]
+    fn merge_of_object_into_itself_should_keep_fields() {
+        let object = object(vec![("x", 1)]);
+        call_native(merge_fields, vec![object.clone(), object.clone()]).unwrap();
+        assert_eq!("{x: 1}", object.to_output_string());
+    }
+
+    #[test]
+    fn arguments_should_be_objects() {
+        let error = call_native(field_names, vec![JexValue::Int(1)]).unwrap_err();
+        assert_eq!(
+            "Argument 0 of field_names must be object but was Int",
+            error.message
+        );
+    }
+}
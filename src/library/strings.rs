@@ -0,0 +1,289 @@
+use crate::define_native;
+use crate::exceptions::runtime_exceptions::{StringIndexOutOfBounds, TypeException};
+use crate::types::JexMachine;
+use crate::values::get_type::GetType;
+use crate::values::to_output_string::ToOutputString;
+use crate::values::values::{JexValue, NativeArity};
+use extendable_vm::Exception;
+use std::convert::TryFrom;
+
+/// Defines the globals that manipulate strings.
+///
+/// Indices and lengths count characters, that is Unicode scalar values, not bytes.
+pub fn define_string_library(machine: &mut JexMachine) {
+    define_native(machine, "substring", NativeArity::Fixed(3), substring);
+    define_native(machine, "char_at", NativeArity::Fixed(2), char_at);
+    define_native(machine, "index_of", NativeArity::Fixed(2), index_of);
+    define_native(machine, "split", NativeArity::Fixed(2), split);
+    define_native(machine, "join", NativeArity::Fixed(2), join);
+    define_native(machine, "trim", NativeArity::Fixed(1), trim);
+    define_native(machine, "to_upper", NativeArity::Fixed(1), to_upper);
+    define_native(machine, "to_lower", NativeArity::Fixed(1), to_lower);
+    define_native(machine, "starts_with", NativeArity::Fixed(2), starts_with);
+    define_native(machine, "ends_with", NativeArity::Fixed(2), ends_with);
+    define_native(machine, "replace", NativeArity::Fixed(3), replace);
+}
+
+fn string_argument<'a>(
+    function: &str,
+    args: &'a [JexValue],
+    index: usize,
+) -> Result<&'a str, Exception> {
+    args[index].as_string().map(String::as_str).ok_or_else(|| {
+        Exception::from(TypeException(format!(
+            "Argument {} of {} must be String but was {}",
+            index,
+            function,
+            args[index].get_type()
+        )))
+    })
+}
+
+fn int_argument(function: &str, args: &[JexValue], index: usize) -> Result<i32, Exception> {
+    args[index].as_int().ok_or_else(|| {
+        Exception::from(TypeException(format!(
+            "Argument {} of {} must be Int but was {}",
+            index,
+            function,
+            args[index].get_type()
+        )))
+    })
+}
+
+/// Converts `index` to a character index that is at most `max`
+fn char_index(index: i32, max: usize, length: usize) -> Result<usize, Exception> {
+    usize::try_from(index)
+        .ok()
+        .filter(|index| *index <= max)
+        .ok_or_else(|| Exception::from(StringIndexOutOfBounds { index, length }))
+}
+
+fn char_count(string: &str) -> Result<JexValue, Exception> {
+    i32::try_from(string.chars().count())
+        .map(JexValue::Int)
+        .map_err(|_| Exception::from(TypeException("String is too long".to_string())))
+}
+
+/// `substring(string, start, end)` returns the characters from `start` up to but not including `end`
+fn substring(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("substring", &args, 0)?;
+    let length = string.chars().count();
+    let end = char_index(int_argument("substring", &args, 2)?, length, length)?;
+    // start must not be greater than end
+    let start = char_index(int_argument("substring", &args, 1)?, end, length)?;
+    let result = string.chars().skip(start).take(end - start).collect();
+    Ok(JexValue::from_string(result))
+}
+
+/// `char_at(string, index)` returns a string of the character at `index`
+fn char_at(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("char_at", &args, 0)?;
+    let index = int_argument("char_at", &args, 1)?;
+    let length = string.chars().count();
+    let char = usize::try_from(index)
+        .ok()
+        .and_then(|index| string.chars().nth(index))
+        .ok_or_else(|| Exception::from(StringIndexOutOfBounds { index, length }))?;
+    Ok(JexValue::from_string(char.to_string()))
+}
+
+/// `index_of(string, part)` returns the index of the first occurrence of `part` or -1
+fn index_of(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("index_of", &args, 0)?;
+    let part = string_argument("index_of", &args, 1)?;
+    match string.find(part) {
+        Some(byte_index) => char_count(&string[..byte_index]),
+        None => Ok(JexValue::Int(-1)),
+    }
+}
+
+/// `split(string, separator)` returns an array of the parts between separators,
+/// an empty separator splits the string into characters
+fn split(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("split", &args, 0)?;
+    let separator = string_argument("split", &args, 1)?;
+    let parts: Vec<JexValue> = if separator.is_empty() {
+        string
+            .chars()
+            .map(|char| JexValue::from_string(char.to_string()))
+            .collect()
+    } else {
+        string
+            .split(separator)
+            .map(|part| JexValue::from_string(part.to_string()))
+            .collect()
+    };
+    Ok(JexValue::from_elements(parts))
+}
+
+/// `join(array, separator)` concatenates the elements of the array with the separator between them,
+/// elements that are not strings are converted like `TO_STRING` does
+fn join(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let array = args[0].as_array().ok_or_else(|| {
+        Exception::from(TypeException(format!(
+            "Argument 0 of join must be Array but was {}",
+            args[0].get_type()
+        )))
+    })?;
+    let separator = string_argument("join", &args, 1)?;
+    let parts: Vec<String> = array
+        .elements()
+        .iter()
+        .map(|element| element.to_output_string())
+        .collect();
+    Ok(JexValue::from_string(parts.join(separator)))
+}
+
+/// `trim(string)` removes whitespace from both ends of the string
+fn trim(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("trim", &args, 0)?;
+    Ok(JexValue::from_string(string.trim().to_string()))
+}
+
+fn to_upper(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("to_upper", &args, 0)?;
+    Ok(JexValue::from_string(string.to_uppercase()))
+}
+
+fn to_lower(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("to_lower", &args, 0)?;
+    Ok(JexValue::from_string(string.to_lowercase()))
+}
+
+fn starts_with(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("starts_with", &args, 0)?;
+    let prefix = string_argument("starts_with", &args, 1)?;
+    Ok(JexValue::Bool(string.starts_with(prefix)))
+}
+
+fn ends_with(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("ends_with", &args, 0)?;
+    let suffix = string_argument("ends_with", &args, 1)?;
+    Ok(JexValue::Bool(string.ends_with(suffix)))
+}
+
+/// `replace(string, from, to)` replaces every occurrence of `from`
+fn replace(_machine: &mut JexMachine, args: Vec<JexValue>) -> Result<JexValue, Exception> {
+    let string = string_argument("replace", &args, 0)?;
+    let from = string_argument("replace", &args, 1)?;
+    let to = string_argument("replace", &args, 2)?;
+    if from.is_empty() {
+        return Ok(args[0].clone());
+    }
+    Ok(JexValue::from_string(string.replace(from, to)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::library::strings::{
+        char_at, index_of, join, replace, split, substring, to_lower, to_upper, trim,
+    };
+    use crate::library::testing::call_native;
+    use crate::values::values::JexValue;
+
+    fn string(string: &str) -> JexValue {
+        JexValue::from_string(string.to_string())
+    }
+
+    #[test]
+    fn substring_should_count_characters() {
+        let result = call_native(
+            substring,
+            vec![string("héllo wörld"), JexValue::Int(1), JexValue::Int(8)],
+        );
+        assert_eq!(string("éllo wö"), result.unwrap());
+        let empty = call_native(
+            substring,
+            vec![string("abc"), JexValue::Int(3), JexValue::Int(3)],
+        );
+        assert_eq!(string(""), empty.unwrap());
+    }
+
+    #[test]
+    fn substring_should_fail_on_bad_indices() {
+        for (start, end) in [(-1, 2), (0, 4), (2, 1)].iter() {
+            let result = call_native(
+                substring,
+                vec![string("abc"), JexValue::Int(*start), JexValue::Int(*end)],
+            );
+            assert_eq!("StringIndexOutOfBounds", result.unwrap_err().name);
+        }
+    }
+
+    #[test]
+    fn char_at_should_return_character_or_fail() {
+        let result = call_native(char_at, vec![string("日本語"), JexValue::Int(2)]);
+        assert_eq!(string("語"), result.unwrap());
+        let error = call_native(char_at, vec![string("日本語"), JexValue::Int(3)]).unwrap_err();
+        assert_eq!("StringIndexOutOfBounds", error.name);
+        let error = call_native(char_at, vec![string("a"), JexValue::Int(-1)]).unwrap_err();
+        assert_eq!("StringIndexOutOfBounds", error.name);
+    }
+
+    #[test]
+    fn index_of_should_return_character_index() {
+        let result = call_native(index_of, vec![string("ёжик в тумане"), string("в")]);
+        assert_eq!(JexValue::Int(5), result.unwrap());
+        let missing = call_native(index_of, vec![string("abc"), string("d")]);
+        assert_eq!(JexValue::Int(-1), missing.unwrap());
+    }
+
+    #[test]
+    fn split_and_join_should_be_inverse() {
+        let parts = call_native(split, vec![string("a,b,,c"), string(",")]).unwrap();
+        assert_eq!("[\"a\", \"b\", \"\", \"c\"]", format!("{:?}", parts));
+        let joined = call_native(join, vec![parts, string(",")]).unwrap();
+        assert_eq!(string("a,b,,c"), joined);
+    }
+
+    #[test]
+    fn split_by_empty_separator_should_return_characters() {
+        let parts = call_native(split, vec![string("añb"), string("")]).unwrap();
+        assert_eq!("[\"a\", \"ñ\", \"b\"]", format!("{:?}", parts));
+    }
+
+    #[test]
+    fn join_should_convert_elements_to_strings() {
+        let array = JexValue::from_elements(vec![JexValue::Int(1), string("x"), JexValue::null()]);
+        let joined = call_native(join, vec![array, string("-")]).unwrap();
+        assert_eq!(string("1-x-null"), joined);
+    }
+
+    #[test]
+    fn case_conversion_should_be_unicode_aware() {
+        assert_eq!(
+            string("STRASSE"),
+            call_native(to_upper, vec![string("straße")]).unwrap()
+        );
+        assert_eq!(
+            string("привет"),
+            call_native(to_lower, vec![string("ПРИВЕТ")]).unwrap()
+        );
+    }
+
+    #[test]
+    fn trim_and_replace_should_work() {
+        assert_eq!(
+            string("a b"),
+            call_native(trim, vec![string("\u{3000} a b\n")]).unwrap()
+        );
+        assert_eq!(
+            string("b-b-b"),
+            call_native(replace, vec![string("a-a-a"), string("a"), string("b")]).unwrap()
+        );
+        assert_eq!(
+            string("abc"),
+            call_native(replace, vec![string("abc"), string(""), string("x")]).unwrap()
+        );
+    }
+
+    #[test]
+    fn natives_should_fail_on_wrong_argument_types() {
+        let error = call_native(trim, vec![JexValue::Int(1)]).unwrap_err();
+        assert_eq!("TypeException", error.name);
+        let error = call_native(char_at, vec![string("a"), string("0")]).unwrap_err();
+        assert_eq!("TypeException", error.name);
+        let error = call_native(join, vec![string("a"), string(",")]).unwrap_err();
+        assert_eq!("TypeException", error.name);
+    }
+}
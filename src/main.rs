@@ -2,39 +2,160 @@ extern crate log;
 extern crate pretty_env_logger;
 
 use clap::{AppSettings, Clap};
-use extendable_vm::{CodeParser, ConstantParserTable, RawBytes};
-use jex_vm::build_jex_machine;
-use jex_vm::code::constant_parsers::JEX_CONSTANT_PARSERS;
+use extendable_vm::Exception;
+use jex_vm::build_jex_machine_with_options;
+use jex_vm::code::assembler::assemble_to_bytes;
+use jex_vm::code::disassembler::disassemble_bytecode;
+use jex_vm::code::loader::{load_bytecode, JexBytecode};
+use jex_vm::code::verifier::verify_code;
+use jex_vm::heap::{GcOptions, JexHeap};
+use jex_vm::instructions::operator_implementations::ArithmeticMode;
+use jex_vm::options::JexMachineOptions;
+use jex_vm::runtime::{run_jex_machine_in_heap, DebugSymbols};
+use std::fs;
+use std::path::Path;
+use std::process;
 
 #[derive(Clap)]
 #[clap(author = "Furetur <furetur@gmail.com>")]
 #[clap(setting = AppSettings::ColoredHelp)]
 struct CliOptions {
+    #[clap(subcommand)]
+    command: Option<Command>,
     #[clap(about = "Path to file that contains bytecode")]
-    input_file: String,
+    input_file: Option<String>,
     #[clap(short, long, about = "Print parsed bytecode chunks and constants")]
     print_parsed: bool,
+    #[clap(
+        long,
+        default_value = "checked",
+        possible_values = &["checked", "wrapping", "saturating"],
+        about = "What integer operators do when the result overflows"
+    )]
+    arithmetic: ArithmeticMode,
+    #[clap(long, about = "Only verify the bytecode without running it")]
+    verify: bool,
+    #[clap(
+        long,
+        conflicts_with = "verify",
+        about = "Run the bytecode without verifying it first"
+    )]
+    no_verify: bool,
+    #[clap(
+        long,
+        default_value = "1024",
+        about = "Number of tracked objects that starts the first garbage collection"
+    )]
+    gc_threshold: usize,
+    #[clap(long, about = "Do not collect unreachable reference cycles")]
+    no_gc: bool,
+    #[clap(
+        long,
+        about = "Print garbage collection statistics after the program finishes"
+    )]
+    gc_stats: bool,
+}
+
+#[derive(Clap)]
+enum Command {
+    #[clap(about = "Print a human-readable listing of the bytecode")]
+    Disasm {
+        #[clap(about = "Path to file that contains bytecode")]
+        input_file: String,
+    },
+    #[clap(about = "Compile a text assembly program into a bytecode file")]
+    Asm {
+        #[clap(about = "Path to file that contains assembly")]
+        input_file: String,
+        #[clap(
+            short,
+            long,
+            about = "Path to the output file, defaults to the input file with `.bytecode` extension"
+        )]
+        output: Option<String>,
+    },
+}
+
+fn read_bytecode(input_file: &str) -> JexBytecode {
+    // read file
+    let bytes = fs::read(input_file).expect("File cannot be opened");
+    // parse file
+    load_bytecode(&bytes).unwrap_or_else(|e| panic!("{}", e))
 }
 
 fn main() {
     pretty_env_logger::init();
 
     let options: CliOptions = CliOptions::parse();
-    // read file
-    let bytes = RawBytes::from_file(&options.input_file).expect("File cannot be opened");
-    // build parser
-    let const_parser_table = ConstantParserTable::parsers(&JEX_CONSTANT_PARSERS);
-    let parser = CodeParser::new(&const_parser_table);
-    // parse file
-    let code = parser.parse(&bytes).unwrap_or_else(|e| panic!("{}", e));
+    match &options.command {
+        Some(Command::Disasm { input_file }) => {
+            let bytecode = read_bytecode(input_file);
+            print!("{}", disassemble_bytecode(&bytecode));
+            return;
+        }
+        Some(Command::Asm { input_file, output }) => {
+            let source = fs::read_to_string(input_file).expect("File cannot be opened");
+            let bytes = assemble_to_bytes(&source).unwrap_or_else(|error| {
+                println!("{}", Exception::from(error));
+                process::exit(1);
+            });
+            let output = output.clone().unwrap_or_else(|| {
+                Path::new(input_file)
+                    .with_extension("bytecode")
+                    .to_string_lossy()
+                    .to_string()
+            });
+            fs::write(&output, bytes).expect("File cannot be written");
+            return;
+        }
+        None => {}
+    }
+    let input_file = options.input_file.as_deref().unwrap_or_else(|| {
+        eprintln!("Path to file that contains bytecode is required");
+        process::exit(2);
+    });
+    let bytecode = read_bytecode(input_file);
+    let code = &bytecode.code;
     if options.print_parsed {
+        println!("{:?}", bytecode.header);
         println!("{:?}", code);
     }
+    // verify
+    if !options.no_verify {
+        if let Err(errors) = verify_code(code) {
+            for error in errors {
+                println!("{}", Exception::from(error));
+            }
+            process::exit(1);
+        }
+        if options.verify {
+            println!("Bytecode is valid");
+            return;
+        }
+    }
     // build machine
-    let mut machine = build_jex_machine(&code);
+    let machine_options = JexMachineOptions {
+        arithmetic_mode: options.arithmetic,
+        gc: GcOptions {
+            enabled: !options.no_gc,
+            threshold: options.gc_threshold,
+            ..GcOptions::default()
+        },
+    };
+    let mut machine = build_jex_machine_with_options(code, &machine_options);
     // start
-    let finished_gracefully = machine.start();
-    if !finished_gracefully {
+    let heap = JexHeap::new(machine_options.gc);
+    let result = run_jex_machine_in_heap(
+        &mut machine,
+        &machine_options,
+        DebugSymbols::of(&bytecode),
+        &heap,
+    );
+    if let Err(error) = result {
+        println!("{}", error);
         println!("There was an exception!");
     }
+    if options.gc_stats {
+        eprintln!("{:?}", heap.stats());
+    }
 }
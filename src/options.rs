@@ -0,0 +1,9 @@
+use crate::heap::GcOptions;
+use crate::instructions::operator_implementations::ArithmeticMode;
+
+/// Settings that change how a `JexMachine` executes code
+#[derive(Debug, Clone, Default)]
+pub struct JexMachineOptions {
+    pub arithmetic_mode: ArithmeticMode,
+    pub gc: GcOptions,
+}
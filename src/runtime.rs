@@ -0,0 +1,208 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::code::debug_info::DebugInfo;
+use crate::code::loader::JexBytecode;
+use crate::code::local_names::LocalNames;
+use crate::heap::JexHeap;
+use crate::instructions::jex_instructions;
+use crate::options::JexMachineOptions;
+use crate::types::JexMachine;
+use crate::values::values::JexValue;
+use extendable_vm::runtime_exceptions::UnknownOpCode;
+use extendable_vm::{ByteReadable, CallFrame, Exception, InstructionTable};
+use log::debug;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A call frame that was active when an exception was raised.
+///
+/// `offset` is the offset of the instruction that the frame was executing,
+/// `location` is its `file:line` if the bytecode has debug info
+/// and `locals` are the named local variables of the frame if the bytecode has local names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackTraceFrame {
+    pub name: String,
+    pub chunk_id: usize,
+    pub offset: usize,
+    pub location: Option<String>,
+    pub locals: Vec<(String, JexValue)>,
+}
+
+impl Display for StackTraceFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} ({})", self.name, location)?,
+            None => write!(f, "{} (#{}:{})", self.name, self.chunk_id, self.offset)?,
+        }
+        if !self.locals.is_empty() {
+            let locals: Vec<String> = self
+                .locals
+                .iter()
+                .map(|(name, value)| format!("{} = {:?}", name, value))
+                .collect();
+            write!(f, ": {}", locals.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Optional sections of the bytecode that make stack traces readable
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DebugSymbols<'a> {
+    pub debug_info: Option<&'a DebugInfo>,
+    pub local_names: Option<&'a LocalNames>,
+}
+
+impl<'a> DebugSymbols<'a> {
+    pub fn of(bytecode: &'a JexBytecode) -> DebugSymbols<'a> {
+        DebugSymbols {
+            debug_info: bytecode.debug_info.as_ref(),
+            local_names: bytecode.local_names.as_ref(),
+        }
+    }
+}
+
+/// An exception that stopped the machine and the call frames that were active, the innermost first
+#[derive(Debug)]
+pub struct JexRuntimeError {
+    pub exception: Exception,
+    pub stack_trace: Vec<StackTraceFrame>,
+}
+
+impl Display for JexRuntimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.exception)?;
+        for frame in &self.stack_trace {
+            write!(f, "\n\tat {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the machine like `Machine::start` but returns the exception instead of printing it.
+///
+/// Stack trace locations and local variables are taken from `symbols`.
+/// If an exception is raised all call frames are discarded while the stack trace is collected.
+pub fn run_jex_machine(
+    machine: &mut JexMachine,
+    options: &JexMachineOptions,
+    symbols: DebugSymbols,
+) -> Result<(), JexRuntimeError> {
+    let heap = JexHeap::new(options.gc);
+    run_jex_machine_in_heap(machine, options, symbols, &heap)
+}
+
+/// Runs the machine like `run_jex_machine` with a heap whose statistics can be read afterwards.
+///
+/// Objects allocated while the machine runs are tracked by `heap`,
+/// unreachable cycles are collected between instructions when the heap reaches its threshold.
+pub fn run_jex_machine_in_heap(
+    machine: &mut JexMachine,
+    options: &JexMachineOptions,
+    symbols: DebugSymbols,
+    heap: &JexHeap,
+) -> Result<(), JexRuntimeError> {
+    let instruction_table =
+        InstructionTable::instructions(jex_instructions(options.arithmetic_mode));
+    let mut current_offset = 0;
+    let result = {
+        let _active_heap = heap.activate();
+        run(machine, &instruction_table, heap, &mut current_offset)
+    };
+    result.map_err(|exception| JexRuntimeError {
+        exception,
+        stack_trace: collect_stack_trace(machine, current_offset, symbols),
+    })
+}
+
+fn run(
+    machine: &mut JexMachine,
+    instruction_table: &InstructionTable<'static, JexConstant, JexValue>,
+    heap: &JexHeap,
+    current_offset: &mut usize,
+) -> Result<(), Exception> {
+    let code = machine.code;
+    loop {
+        let ip = match machine.instruction_pointer() {
+            Ok(ip) => ip,
+            Err(_) => return Ok(()),
+        };
+        *current_offset = ip.instruction_pointer;
+        let op_code = match code.read(ip) {
+            Some(op_code) => op_code,
+            None => return Ok(()),
+        };
+        let instruction = instruction_table
+            .get_instruction(op_code)
+            .ok_or(UnknownOpCode(op_code))?;
+        let arguments_ip = machine.instruction_pointer()?.clone();
+        machine
+            .instruction_pointer()?
+            .jump_forward(instruction.instruction_fn.byte_arity());
+        debug!("Running instruction {}.", instruction.name);
+        instruction.instruction_fn.run(machine, arguments_ip)?;
+        if heap.should_collect() {
+            let collected = heap.collect(machine);
+            debug!("Collected {} unreachable objects.", collected);
+        }
+    }
+}
+
+fn collect_stack_trace(
+    machine: &mut JexMachine,
+    current_offset: usize,
+    symbols: DebugSymbols,
+) -> Vec<StackTraceFrame> {
+    let mut stack_trace = vec![];
+    while let Ok(frame) = machine.peek_frame() {
+        let chunk_id = frame.instruction_pointer.chunk_id;
+        // callers are inside of their call instruction which ends right before the instruction pointer
+        let offset = if stack_trace.is_empty() {
+            current_offset
+        } else {
+            frame
+                .instruction_pointer
+                .instruction_pointer
+                .saturating_sub(1)
+        };
+        let location = symbols
+            .debug_info
+            .and_then(|debug_info| debug_info.location_at(chunk_id, offset));
+        let locals = symbols
+            .local_names
+            .map(|local_names| frame_locals(machine, frame, local_names, offset))
+            .unwrap_or_default();
+        stack_trace.push(StackTraceFrame {
+            name: frame.name.clone(),
+            chunk_id,
+            offset,
+            location,
+            locals,
+        });
+        // the frame is discarded only after its locals are read
+        let _ = machine.discard_frame();
+    }
+    stack_trace
+}
+
+/// Returns the values of the named local variables of `frame` that are live at `offset`.
+///
+/// Variables whose slots are not on the operand stack are skipped.
+pub fn frame_locals(
+    machine: &JexMachine,
+    frame: &CallFrame,
+    local_names: &LocalNames,
+    offset: usize,
+) -> Vec<(String, JexValue)> {
+    let chunk = match local_names.get_chunk(frame.chunk_id) {
+        Some(chunk) => chunk,
+        None => return vec![],
+    };
+    chunk
+        .live_at(offset)
+        .into_iter()
+        .filter_map(|local| {
+            let value = machine.get_operand(frame.start_slot + local.slot).ok()?;
+            Some((local.name.clone(), value.dereference()))
+        })
+        .collect()
+}
@@ -8,11 +8,16 @@ impl GetType for JexValue {
     fn get_type(&self) -> String {
         match self {
             JexValue::Int(int) => int.get_type(),
+            JexValue::Float(float) => float.get_type(),
             JexValue::Bool(bool) => bool.get_type(),
-            JexValue::Object(obj) => (&**obj).get_type(),
+            JexValue::Object(obj) => (**obj).get_type(),
             JexValue::Function(func) => func.get_type(),
             JexValue::Null(null) => null.get_type(),
             JexValue::Instance(_) => "object".to_string(),
+            JexValue::Array(_) => "Array".to_string(),
+            JexValue::Map(_) => "Map".to_string(),
+            JexValue::Class(_) => "class".to_string(),
+            JexValue::Cell(cell) => cell.get().get_type(),
         }
     }
 }
@@ -23,6 +28,12 @@ impl GetType for i32 {
     }
 }
 
+impl GetType for f64 {
+    fn get_type(&self) -> String {
+        "Float".to_string()
+    }
+}
+
 impl GetType for bool {
     fn get_type(&self) -> String {
         "Boolean".to_string()
@@ -38,7 +49,10 @@ impl GetType for JexObject {
 impl GetType for JexFunction {
     fn get_type(&self) -> String {
         match self {
-            JexFunction::Function { .. } => "fn".to_string(),
+            JexFunction::Function { .. }
+            | JexFunction::Closure(_)
+            | JexFunction::BoundMethod(_)
+            | JexFunction::Native(_) => "fn".to_string(),
             JexFunction::Script => "<script>".to_string(),
         }
     }
@@ -0,0 +1,540 @@
+use crate::exceptions::runtime_exceptions::{JsonEncodeException, JsonParseException};
+use crate::values::get_type::GetType;
+use crate::values::to_output_string::ToOutputString;
+use crate::values::values::JexValue;
+use std::fmt::Write;
+
+/// Arrays and objects nested deeper than this are not parsed or encoded so that neither can overflow the stack
+const MAX_DEPTH: usize = 512;
+
+/// Encodes the value as compact JSON.
+///
+/// Objects become JSON objects of their own fields and maps with string keys become JSON objects,
+/// both in the insertion order. Functions, classes, maps with other keys, infinite and `NaN` floats,
+/// values that contain themselves and values nested deeper than `MAX_DEPTH` cannot be encoded.
+pub fn to_json(value: &JexValue) -> Result<String, JsonEncodeException> {
+    let mut json = String::new();
+    encode(value, &mut json, &mut vec![])?;
+    Ok(json)
+}
+
+/// Decodes JSON text, objects become new objects, arrays become arrays
+/// and numbers become ints if they are integers that fit into an `i32` and floats otherwise
+pub fn from_json(text: &str) -> Result<JexValue, JsonParseException> {
+    let mut parser = JsonParser {
+        chars: text.chars().collect(),
+        position: 0,
+    };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return Err(parser.unexpected());
+    }
+    Ok(value)
+}
+
+fn encode(
+    value: &JexValue,
+    json: &mut String,
+    visited: &mut Vec<*const ()>,
+) -> Result<(), JsonEncodeException> {
+    match value {
+        JexValue::Null(_) => json.push_str("null"),
+        JexValue::Bool(bool) => json.push_str(&bool.to_string()),
+        JexValue::Int(int) => json.push_str(&int.to_string()),
+        JexValue::Float(float) if float.is_finite() => json.push_str(&float.to_output_string()),
+        JexValue::Object(_) => encode_string(&value.to_output_string(), json),
+        JexValue::Array(array) => {
+            enter(visited, &**array as *const _ as *const ())?;
+            json.push('[');
+            for (index, element) in array.elements().iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                encode(element, json, visited)?;
+            }
+            json.push(']');
+            visited.pop();
+        }
+        JexValue::Map(map) => {
+            enter(visited, &**map as *const _ as *const ())?;
+            let mut fields = vec![];
+            for (key, value) in map.entries() {
+                match key.as_string() {
+                    Some(key) => fields.push((key.clone(), value)),
+                    None => {
+                        return Err(JsonEncodeException(format!(
+                            "Map key {:?} cannot be encoded as JSON, only String keys can",
+                            key
+                        )))
+                    }
+                }
+            }
+            encode_object(&fields, json, visited)?;
+            visited.pop();
+        }
+        JexValue::Instance(instance) => {
+            enter(visited, &**instance as *const _ as *const ())?;
+            encode_object(&instance.fields(), json, visited)?;
+            visited.pop();
+        }
+        JexValue::Cell(cell) => encode(&cell.get(), json, visited)?,
+        JexValue::Float(_) | JexValue::Function(_) | JexValue::Class(_) => {
+            return Err(JsonEncodeException(format!(
+                "{} of type {} cannot be encoded as JSON",
+                value.to_output_string(),
+                value.get_type()
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Pushes the array, map or object to `visited` which holds every value that encloses it
+fn enter(visited: &mut Vec<*const ()>, pointer: *const ()) -> Result<(), JsonEncodeException> {
+    if visited.len() > MAX_DEPTH {
+        return Err(JsonEncodeException(format!(
+            "Value is nested deeper than {} levels and cannot be encoded as JSON",
+            MAX_DEPTH
+        )));
+    }
+    if visited.contains(&pointer) {
+        return Err(JsonEncodeException(
+            "Value contains itself and cannot be encoded as JSON".to_string(),
+        ));
+    }
+    visited.push(pointer);
+    Ok(())
+}
+
+fn encode_object(
+    fields: &[(String, JexValue)],
+    json: &mut String,
+    visited: &mut Vec<*const ()>,
+) -> Result<(), JsonEncodeException> {
+    json.push('{');
+    for (index, (name, value)) in fields.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        encode_string(name, json);
+        json.push(':');
+        encode(value, json, visited)?;
+    }
+    json.push('}');
+    Ok(())
+}
+
+fn encode_string(string: &str, json: &mut String) {
+    json.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '\u{8}' => json.push_str("\\b"),
+            '\u{c}' => json.push_str("\\f"),
+            char if char < ' ' => write!(json, "\\u{:04x}", char as u32).unwrap(),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl JsonParser {
+    fn error(&self, message: &str) -> JsonParseException {
+        JsonParseException {
+            message: message.to_string(),
+            position: self.position,
+        }
+    }
+
+    fn unexpected(&self) -> JsonParseException {
+        match self.peek() {
+            Some(char) => self.error(&format!("Unexpected character {:?}", char)),
+            None => self.error("Unexpected end of JSON"),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonParseException> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<JexValue, JsonParseException> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("JSON is nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => Ok(JexValue::from_string(self.string()?)),
+            Some('t') => self.literal("true", JexValue::Bool(true)),
+            Some('f') => self.literal("false", JexValue::Bool(false)),
+            Some('n') => self.literal("null", JexValue::null()),
+            Some('-') | Some('0'..='9') => self.number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: JexValue) -> Result<JexValue, JsonParseException> {
+        for expected in word.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn object(&mut self, depth: usize) -> Result<JexValue, JsonParseException> {
+        self.expect('{')?;
+        let object = JexValue::new_object();
+        let instance = object.as_instance().unwrap();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(object);
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected());
+            }
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value(depth + 1)?;
+            instance.put_field(name, value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(object);
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<JexValue, JsonParseException> {
+        self.expect('[')?;
+        let mut elements = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(JexValue::from_elements(elements));
+        }
+        loop {
+            elements.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(JexValue::from_elements(elements));
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonParseException> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.position += 1;
+                    return Ok(string);
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    string.push(self.escape()?);
+                }
+                Some(char) if char < ' ' => {
+                    return Err(self.error("Control character in string"));
+                }
+                Some(char) => {
+                    self.position += 1;
+                    string.push(char);
+                }
+                None => return Err(self.error("Unterminated string")),
+            }
+        }
+    }
+
+    /// Reads an escape sequence after the backslash
+    fn escape(&mut self) -> Result<char, JsonParseException> {
+        let char = match self.peek() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                self.position += 1;
+                return self.unicode_escape();
+            }
+            _ => return Err(self.error("Invalid escape sequence")),
+        };
+        self.position += 1;
+        Ok(char)
+    }
+
+    /// Reads the hex digits of `\uXXXX`, a surrogate pair is two escapes in a row
+    fn unicode_escape(&mut self) -> Result<char, JsonParseException> {
+        let start = self.position;
+        let first = self.hex_code()?;
+        let code = if (0xD800..0xDC00).contains(&first) {
+            if self.peek() != Some('\\') || self.chars.get(self.position + 1) != Some(&'u') {
+                return Err(JsonParseException {
+                    message: "Unpaired surrogate in string".to_string(),
+                    position: start,
+                });
+            }
+            self.position += 2;
+            let second = self.hex_code()?;
+            if !(0xDC00..0xE000).contains(&second) {
+                return Err(JsonParseException {
+                    message: "Unpaired surrogate in string".to_string(),
+                    position: start,
+                });
+            }
+            0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00)
+        } else {
+            first
+        };
+        std::char::from_u32(code).ok_or(JsonParseException {
+            message: "Unpaired surrogate in string".to_string(),
+            position: start,
+        })
+    }
+
+    fn hex_code(&mut self) -> Result<u32, JsonParseException> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .peek()
+                .and_then(|char| char.to_digit(16))
+                .ok_or_else(|| self.error("Invalid unicode escape"))?;
+            code = code * 16 + digit;
+            self.position += 1;
+        }
+        Ok(code)
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some('0'..='9') = self.peek() {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Result<JexValue, JsonParseException> {
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.position += 1;
+        }
+        if self.peek() == Some('0') {
+            self.position += 1;
+        } else if self.digits() == 0 {
+            return Err(self.unexpected());
+        }
+        let mut is_integer = true;
+        if self.peek() == Some('.') {
+            is_integer = false;
+            self.position += 1;
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            is_integer = false;
+            self.position += 1;
+            if let Some('+') | Some('-') = self.peek() {
+                self.position += 1;
+            }
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        if is_integer {
+            if let Ok(int) = text.parse::<i32>() {
+                return Ok(JexValue::Int(int));
+            }
+        }
+        text.parse::<f64>()
+            .map(JexValue::Float)
+            .map_err(|_| JsonParseException {
+                message: "Invalid number".to_string(),
+                position: start,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::values::json::{from_json, to_json};
+    use crate::values::values::{JexFunction, JexValue};
+
+    fn string(string: &str) -> JexValue {
+        JexValue::from_string(string.to_string())
+    }
+
+    #[test]
+    fn it_should_encode_scalars() {
+        assert_eq!("null", to_json(&JexValue::null()).unwrap());
+        assert_eq!("true", to_json(&JexValue::Bool(true)).unwrap());
+        assert_eq!("-3", to_json(&JexValue::Int(-3)).unwrap());
+        assert_eq!("2.0", to_json(&JexValue::Float(2.0)).unwrap());
+        assert_eq!(
+            "\"a\\\"b\\\\\\n\\u0001é\"",
+            to_json(&string("a\"b\\\n\u{1}é")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_encode_objects_arrays_and_maps() {
+        let object = JexValue::new_object();
+        let instance = object.as_instance().unwrap();
+        instance.put_field("name".to_string(), string("a"));
+        instance.put_field(
+            "tags".to_string(),
+            JexValue::from_elements(vec![JexValue::Int(1), JexValue::null()]),
+        );
+        instance.put_field("age".to_string(), JexValue::Int(3));
+        assert_eq!(
+            "{\"name\":\"a\",\"tags\":[1,null],\"age\":3}",
+            to_json(&object).unwrap()
+        );
+        let map = JexValue::new_map();
+        map.as_map().unwrap().insert(string("z"), object).unwrap();
+        map.as_map()
+            .unwrap()
+            .insert(string("a"), JexValue::Int(1))
+            .unwrap();
+        assert_eq!(
+            "{\"z\":{\"name\":\"a\",\"tags\":[1,null],\"age\":3},\"a\":1}",
+            to_json(&map).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_not_encode_functions_cycles_and_non_finite_floats() {
+        let function = JexValue::Function(JexFunction::Function {
+            arity: 0,
+            chunk_id: 1,
+            name: "f".to_string(),
+        });
+        assert!(to_json(&function).is_err());
+        assert!(to_json(&JexValue::Float(f64::NAN)).is_err());
+        let map = JexValue::new_map();
+        map.as_map()
+            .unwrap()
+            .insert(JexValue::Int(1), JexValue::Int(1))
+            .unwrap();
+        assert!(to_json(&map).is_err());
+
+        let object = JexValue::new_object();
+        let array = JexValue::from_elements(vec![object.clone()]);
+        object
+            .as_instance()
+            .unwrap()
+            .put_field("array".to_string(), array);
+        let error = to_json(&object).unwrap_err();
+        assert!(error.0.contains("contains itself"));
+        object.as_instance().unwrap().clear();
+    }
+
+    #[test]
+    fn shared_values_that_are_not_cycles_should_be_encoded() {
+        let shared = JexValue::from_elements(vec![]);
+        let array = JexValue::from_elements(vec![shared.clone(), shared]);
+        assert_eq!("[[],[]]", to_json(&array).unwrap());
+    }
+
+    #[test]
+    fn it_should_decode_values() {
+        let value = from_json(
+            " {\"name\": \"a\\u00e9\\ud83d\\ude00\", \"list\": [1, -2.5, 1e2, 3000000000, true, null, {}]} ",
+        )
+        .unwrap();
+        let instance = value.as_instance().unwrap();
+        assert_eq!(string("aé😀"), instance.get_field("name").unwrap());
+        assert_eq!(
+            "[1, -2.5, 100.0, 3000000000.0, true, null, {}]",
+            format!("{:?}", instance.get_field("list").unwrap())
+        );
+    }
+
+    #[test]
+    fn decoded_json_should_encode_back() {
+        let json = "{\"a\":[1,2.5,\"x\"],\"b\":{\"c\":null}}";
+        assert_eq!(json, to_json(&from_json(json).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn malformed_json_should_fail_with_position() {
+        let cases = [
+            ("", 0),
+            ("[1, 2", 5),
+            ("[1,]", 3),
+            ("{\"a\" 1}", 5),
+            ("{a: 1}", 1),
+            ("tru", 3),
+            ("01", 1),
+            ("1.", 2),
+            ("\"abc", 4),
+            ("\"\\x\"", 2),
+            ("\"\\ud800\"", 3),
+            ("[] []", 3),
+            ("-", 1),
+        ];
+        for (json, position) in cases.iter() {
+            let error = from_json(json).unwrap_err();
+            assert_eq!(*position, error.position, "{:?}: {}", json, error.message);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_json_should_fail_instead_of_overflowing() {
+        let json = "[".repeat(100_000);
+        assert!(from_json(&json).is_err());
+    }
+
+    #[test]
+    fn deeply_nested_value_should_fail_instead_of_overflowing() {
+        let deepest_json = format!("{}{}", "[".repeat(513), "]".repeat(513));
+        let mut value = from_json(&deepest_json).unwrap();
+        assert_eq!(deepest_json, to_json(&value).unwrap());
+        value = JexValue::from_elements(vec![value]);
+        let error = to_json(&value).unwrap_err();
+        assert!(error.0.contains("nested deeper than 512 levels"));
+    }
+}
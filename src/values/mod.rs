@@ -1,3 +1,5 @@
 pub mod get_type;
+pub mod json;
 pub mod to_output_string;
+#[allow(clippy::module_inception)]
 pub mod values;
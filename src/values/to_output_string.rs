@@ -1,4 +1,6 @@
-use crate::values::values::{JexFunction, JexNull, JexObject, JexValue};
+use crate::values::values::{
+    JexArray, JexClass, JexFunction, JexInstance, JexMap, JexNull, JexObject, JexValue, NativeArity,
+};
 use std::rc::Rc;
 
 pub trait ToOutputString {
@@ -10,14 +12,153 @@ impl ToOutputString for JexValue {
         match self {
             JexValue::Null(null) => null.to_output_string(),
             JexValue::Int(int) => int.to_output_string(),
+            JexValue::Float(float) => float.to_output_string(),
             JexValue::Bool(bool) => bool.to_output_string(),
             JexValue::Function(func) => func.to_output_string(),
             JexValue::Object(obj) => obj.to_output_string(),
-            JexValue::Instance(_) => "object".to_string(),
+            JexValue::Instance(instance) => instance.to_output_string(),
+            JexValue::Array(array) => array.to_output_string(),
+            JexValue::Map(map) => map.to_output_string(),
+            JexValue::Class(class) => class.to_output_string(),
+            JexValue::Cell(cell) => cell.get().to_output_string(),
         }
     }
 }
 
+impl ToOutputString for JexArray {
+    fn to_output_string(&self) -> String {
+        ValueFormatter::new(false).array(self, 0)
+    }
+}
+
+impl ToOutputString for JexMap {
+    fn to_output_string(&self) -> String {
+        ValueFormatter::new(false).map(self, 0)
+    }
+}
+
+impl ToOutputString for JexInstance {
+    fn to_output_string(&self) -> String {
+        ValueFormatter::new(false).instance(self, 0)
+    }
+}
+
+/// Formats the value like `to_output_string` but puts every element, entry and field of a non-empty array,
+/// map or object on its own line indented by two spaces per level of nesting
+pub fn to_pretty_string(value: &JexValue) -> String {
+    let mut formatter = ValueFormatter::new(true);
+    match value {
+        JexValue::Array(array) => formatter.array(array, 0),
+        JexValue::Map(map) => formatter.map(map, 0),
+        JexValue::Instance(instance) => formatter.instance(instance, 0),
+        _ => value.to_output_string(),
+    }
+}
+
+/// Formats arrays, maps and objects whose elements are formatted like `Debug` so that strings are quoted.
+///
+/// `visited` contains the collections and the objects that are being formatted,
+/// one that contains itself is printed as `[...]` or `{...}`.
+struct ValueFormatter {
+    multi_line: bool,
+    visited: Vec<*const ()>,
+}
+
+impl ValueFormatter {
+    fn new(multi_line: bool) -> ValueFormatter {
+        ValueFormatter {
+            multi_line,
+            visited: vec![],
+        }
+    }
+
+    fn element(&mut self, element: &JexValue, depth: usize) -> String {
+        match element {
+            JexValue::Array(array) => self.array(array, depth),
+            JexValue::Map(map) => self.map(map, depth),
+            JexValue::Instance(instance) => self.instance(instance, depth),
+            _ => format!("{:?}", element),
+        }
+    }
+
+    fn array(&mut self, array: &JexArray, depth: usize) -> String {
+        let pointer = array as *const JexArray as *const ();
+        if self.visited.contains(&pointer) {
+            return "[...]".to_string();
+        }
+        self.visited.push(pointer);
+        let elements: Vec<String> = array
+            .elements()
+            .iter()
+            .map(|element| self.element(element, depth + 1))
+            .collect();
+        self.visited.pop();
+        self.enclose("[", elements, "]", depth)
+    }
+
+    fn map(&mut self, map: &JexMap, depth: usize) -> String {
+        let pointer = map as *const JexMap as *const ();
+        if self.visited.contains(&pointer) {
+            return "{...}".to_string();
+        }
+        self.visited.push(pointer);
+        let entries: Vec<String> = map
+            .entries()
+            .iter()
+            .map(|(key, value)| format!("{:?}: {}", key, self.element(value, depth + 1)))
+            .collect();
+        self.visited.pop();
+        self.enclose("{", entries, "}", depth)
+    }
+
+    /// Objects are printed with their own fields in the order they were first set, objects of classes start with the class name
+    fn instance(&mut self, instance: &JexInstance, depth: usize) -> String {
+        let prefix = match instance.class() {
+            Some(class) => format!("{} ", class.name),
+            None => String::new(),
+        };
+        let pointer = instance as *const JexInstance as *const ();
+        if self.visited.contains(&pointer) {
+            return format!("{}{{...}}", prefix);
+        }
+        self.visited.push(pointer);
+        let fields: Vec<String> = instance
+            .fields()
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, self.element(value, depth + 1)))
+            .collect();
+        self.visited.pop();
+        prefix + &self.enclose("{", fields, "}", depth)
+    }
+
+    fn enclose(&self, open: &str, items: Vec<String>, close: &str, depth: usize) -> String {
+        if items.is_empty() {
+            format!("{}{}", open, close)
+        } else if self.multi_line {
+            let indent = "  ".repeat(depth + 1);
+            let lines: Vec<String> = items
+                .iter()
+                .map(|item| format!("{}{}", indent, item))
+                .collect();
+            format!(
+                "{}\n{}\n{}{}",
+                open,
+                lines.join(",\n"),
+                "  ".repeat(depth),
+                close
+            )
+        } else {
+            format!("{}{}{}", open, items.join(", "), close)
+        }
+    }
+}
+
+impl ToOutputString for JexClass {
+    fn to_output_string(&self) -> String {
+        format!("class {}", self.name)
+    }
+}
+
 impl ToOutputString for JexNull {
     fn to_output_string(&self) -> String {
         "null".to_string()
@@ -30,6 +171,17 @@ impl ToOutputString for i32 {
     }
 }
 
+impl ToOutputString for f64 {
+    fn to_output_string(&self) -> String {
+        // integral floats keep their fractional part so that they are not confused with ints
+        if self.is_finite() && self.fract() == 0.0 && self.abs() < 1e16 {
+            format!("{:.1}", self)
+        } else {
+            self.to_string()
+        }
+    }
+}
+
 impl ToOutputString for bool {
     fn to_output_string(&self) -> String {
         self.to_string()
@@ -38,7 +190,7 @@ impl ToOutputString for bool {
 
 impl ToOutputString for Rc<JexObject> {
     fn to_output_string(&self) -> String {
-        (&**self).to_output_string()
+        (**self).to_output_string()
     }
 }
 
@@ -56,6 +208,18 @@ impl ToOutputString for JexFunction {
             JexFunction::Function { name, arity, .. } => {
                 format!("function {}({} params)", name, arity)
             }
+            JexFunction::Closure(closure) => {
+                format!("function {}({} params)", closure.name, closure.arity)
+            }
+            JexFunction::BoundMethod(bound) => {
+                format!("bound {}", bound.method.to_output_string())
+            }
+            JexFunction::Native(native) => match native.arity {
+                NativeArity::Fixed(arity) => {
+                    format!("native function {}({} params)", native.name, arity)
+                }
+                NativeArity::Variadic => format!("native function {}(...)", native.name),
+            },
         }
     }
 }
@@ -1,21 +1,31 @@
+use crate::code::bytecode_constants::JexConstant;
+use crate::exceptions::runtime_exceptions::{InvalidMapKey, PrototypeCycle};
 use crate::exceptions::static_exceptions::{InvalidFunctionChunk, NotFoundChunkForFunction};
+use crate::heap;
 use crate::types::JexMachine;
-use crate::values::to_output_string::ToOutputString;
-use extendable_vm::Exception;
-use std::cell::{Cell, RefCell};
+use crate::values::to_output_string::{to_pretty_string, ToOutputString};
+use extendable_vm::{Code, Exception};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 #[derive(Clone)]
 pub enum JexValue {
     Null(JexNull),
     Int(i32),
+    Float(f64),
     Bool(bool),
     Object(Rc<JexObject>),
     Instance(Rc<JexInstance>),
+    Array(Rc<JexArray>),
+    Map(Rc<JexMap>),
+    Class(Rc<JexClass>),
     Function(JexFunction),
+    /// A local variable that was captured by a closure, only slots of call frames contain cells
+    Cell(Rc<JexCell>),
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -26,7 +36,7 @@ pub enum JexObject {
     String(String),
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum JexFunction {
     Script,
     Function {
@@ -34,22 +44,120 @@ pub enum JexFunction {
         chunk_id: usize,
         name: String,
     },
+    Closure(Rc<JexClosure>),
+    BoundMethod(Rc<JexBoundMethod>),
+    Native(Rc<JexNativeFunction>),
+}
+
+/// Number of arguments that a native function accepts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NativeArity {
+    Fixed(usize),
+    Variadic,
+}
+
+pub type NativeFn = dyn Fn(&mut JexMachine, Vec<JexValue>) -> Result<JexValue, Exception>;
+
+/// A function implemented in Rust that bytecode can call like any other function.
+///
+/// The function receives the machine and the arguments and returns the result of the call.
+pub struct JexNativeFunction {
+    pub name: String,
+    pub arity: NativeArity,
+    pub function: Box<NativeFn>,
+}
+
+/// A function together with the variables that it captured from the enclosing functions
+pub struct JexClosure {
+    pub chunk_id: usize,
+    pub arity: usize,
+    pub name: String,
+    pub upvalues: Vec<Rc<JexCell>>,
+}
+
+/// A method together with the instance that it was read from
+pub struct JexBoundMethod {
+    pub receiver: JexValue,
+    pub method: JexFunction,
+}
+
+/// A class whose instances share its methods
+pub struct JexClass {
+    pub name: String,
+    methods: RefCell<HashMap<String, JexFunction>>,
+}
+
+/// A captured local variable.
+///
+/// The slot of the variable holds the cell so that the call frame and the closures share the variable,
+/// the cell lives on after the call frame is discarded.
+pub struct JexCell {
+    value: RefCell<JexValue>,
 }
 
 pub struct JexInstance {
-    fields: RefCell<HashMap<String, JexValue>>,
+    fields: RefCell<InstanceFields>,
+    class: Option<Rc<JexClass>>,
+    /// Instance whose fields are found when the instance does not have them
+    prototype: RefCell<Option<Rc<JexInstance>>>,
 }
 
+pub struct JexArray {
+    elements: RefCell<Vec<JexValue>>,
+}
+
+/// A dictionary that remembers the order in which keys were inserted
+pub struct JexMap {
+    entries: RefCell<MapEntries>,
+}
+
+#[derive(Default)]
+struct MapEntries {
+    /// Index of the entry with the key in `entries`
+    indices: HashMap<MapKey, usize>,
+    entries: Vec<(JexValue, JexValue)>,
+}
+
+/// Fields of an instance that remember the order in which they were first set
+#[derive(Default)]
+struct InstanceFields {
+    /// Index of the field with the name in `fields`
+    indices: HashMap<String, usize>,
+    fields: Vec<(String, JexValue)>,
+}
+
+/// A value that can be used as a key of a map, such values are equal to themselves
+#[derive(PartialEq, Hash)]
+struct MapKey(JexValue);
+
+impl Eq for MapKey {}
+
 impl JexValue {
     pub fn null() -> JexValue {
         JexValue::Null(JexNull)
     }
     pub fn new_object() -> JexValue {
-        JexValue::Instance(Default::default())
+        heap::track(JexValue::Instance(Default::default()))
+    }
+    pub fn new_instance_of(class: Rc<JexClass>) -> JexValue {
+        heap::track(JexValue::Instance(Rc::new(JexInstance::of_class(class))))
     }
     pub fn from_string(string: String) -> JexValue {
         JexValue::Object(Rc::new(JexObject::String(string)))
     }
+    pub fn from_elements(elements: Vec<JexValue>) -> JexValue {
+        heap::track(JexValue::Array(Rc::new(JexArray::new(elements))))
+    }
+    pub fn new_map() -> JexValue {
+        heap::track(JexValue::Map(Rc::new(JexMap::new())))
+    }
+    /// Only ints, strings, booleans and null can be used as keys of maps
+    pub fn is_valid_map_key(&self) -> bool {
+        matches!(
+            self,
+            JexValue::Int(_) | JexValue::Bool(_) | JexValue::Null(_) | JexValue::Object(_)
+        )
+    }
     pub fn as_int(&self) -> Option<i32> {
         if let JexValue::Int(i) = self {
             Some(*i)
@@ -57,6 +165,13 @@ impl JexValue {
             None
         }
     }
+    pub fn as_float(&self) -> Option<f64> {
+        if let JexValue::Float(float) = self {
+            Some(*float)
+        } else {
+            None
+        }
+    }
     pub fn as_bool(&self) -> Option<bool> {
         if let JexValue::Bool(bool) = self {
             Some(*bool)
@@ -89,16 +204,61 @@ impl JexValue {
             None
         }
     }
+    pub fn as_array(&self) -> Option<&JexArray> {
+        if let JexValue::Array(array) = self {
+            Some(&**array)
+        } else {
+            None
+        }
+    }
+    pub fn as_map(&self) -> Option<&JexMap> {
+        if let JexValue::Map(map) = self {
+            Some(&**map)
+        } else {
+            None
+        }
+    }
+    /// Returns the value of the variable that is stored in a slot, which is the content of the cell if the variable was captured
+    pub fn dereference(&self) -> JexValue {
+        if let JexValue::Cell(cell) = self {
+            cell.get()
+        } else {
+            self.clone()
+        }
+    }
 }
 
 impl JexFunction {
     pub fn from_code(machine: &JexMachine, chunk_id: usize) -> Result<JexFunction, Exception> {
-        let chunk = machine
-            .code
+        JexFunction::from_chunk(machine.code, chunk_id)
+    }
+    /// Returns the chunk id, the arity and the name of the code that is run when the function is called.
+    ///
+    /// `None` is returned for the script which cannot be called and for native functions which do not have chunks.
+    pub fn signature(&self) -> Option<(usize, usize, &str)> {
+        match self {
+            JexFunction::Script | JexFunction::Native(_) => None,
+            JexFunction::Function {
+                chunk_id,
+                arity,
+                name,
+            } => Some((*chunk_id, *arity, name)),
+            JexFunction::Closure(closure) => Some((closure.chunk_id, closure.arity, &closure.name)),
+            JexFunction::BoundMethod(bound) => bound.method.signature(),
+        }
+    }
+    /// Reads the name and the arity of a function from the first two constants of the chunk
+    pub fn from_chunk(code: &Code<JexConstant>, chunk_id: usize) -> Result<JexFunction, Exception> {
+        let chunk = code
             .get_chunk(chunk_id)
             .ok_or(NotFoundChunkForFunction(chunk_id))?;
-        let name = chunk.constants[0].as_string()?;
-        let read_arity = chunk.constants[1].as_int()?;
+        let invalid_chunk = || Exception::from(InvalidFunctionChunk(chunk_id));
+        let name = chunk
+            .constants
+            .first()
+            .ok_or_else(invalid_chunk)?
+            .as_string()?;
+        let read_arity = chunk.constants.get(1).ok_or_else(invalid_chunk)?.as_int()?;
         let arity = usize::try_from(read_arity);
         if let Ok(usize) = arity {
             Ok(JexFunction::Function {
@@ -112,20 +272,329 @@ impl JexFunction {
     }
 }
 
+impl JexClass {
+    pub fn new(name: String) -> JexClass {
+        JexClass {
+            name,
+            methods: RefCell::new(HashMap::new()),
+        }
+    }
+    pub fn get_method(&self, name: &str) -> Option<JexFunction> {
+        self.methods.borrow().get(name).cloned()
+    }
+    pub fn put_method(&self, name: String, method: JexFunction) {
+        self.methods.borrow_mut().insert(name, method);
+    }
+    pub fn methods(&self) -> Vec<JexFunction> {
+        self.methods.borrow().values().cloned().collect()
+    }
+}
+
+impl JexCell {
+    pub fn new(value: JexValue) -> JexCell {
+        JexCell {
+            value: RefCell::new(value),
+        }
+    }
+    pub fn get(&self) -> JexValue {
+        self.value.borrow().clone()
+    }
+    pub fn set(&self, value: JexValue) {
+        *self.value.borrow_mut() = value;
+    }
+}
+
+/// Closures are equal only to themselves
+impl PartialEq for JexClosure {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for JexClosure {}
+
+impl Hash for JexClosure {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state)
+    }
+}
+
+/// Native functions are equal only to themselves
+impl PartialEq for JexNativeFunction {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for JexNativeFunction {}
+
+impl Hash for JexNativeFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state)
+    }
+}
+
+impl Debug for JexNativeFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "native {} {:?}", self.name, self.arity)
+    }
+}
+
+/// Bound methods are equal only to themselves
+impl PartialEq for JexBoundMethod {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for JexBoundMethod {}
+
+impl Hash for JexBoundMethod {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state)
+    }
+}
+
+impl Debug for JexBoundMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bound {:?}", self.method)
+    }
+}
+
+impl Debug for JexClosure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "closure {} with {} upvalues",
+            self.name,
+            self.upvalues.len()
+        )
+    }
+}
+
 impl JexInstance {
     pub fn new() -> JexInstance {
         JexInstance {
-            fields: RefCell::new(HashMap::new()),
+            fields: RefCell::new(InstanceFields::default()),
+            class: None,
+            prototype: RefCell::new(None),
         }
     }
+    pub fn of_class(class: Rc<JexClass>) -> JexInstance {
+        JexInstance {
+            fields: RefCell::new(InstanceFields::default()),
+            class: Some(class),
+            prototype: RefCell::new(None),
+        }
+    }
+    pub fn class(&self) -> Option<&Rc<JexClass>> {
+        self.class.as_ref()
+    }
     pub fn is_empty(&self) -> bool {
-        self.fields.borrow().is_empty()
+        self.fields.borrow().fields.is_empty()
+    }
+    pub fn field_count(&self) -> usize {
+        self.fields.borrow().fields.len()
     }
     pub fn get_field(&self, name: &str) -> Option<JexValue> {
-        self.fields.borrow().get(name).cloned()
+        let fields = self.fields.borrow();
+        fields
+            .indices
+            .get(name)
+            .map(|index| fields.fields[*index].1.clone())
+    }
+    /// Own fields in the order they were first set
+    pub fn fields(&self) -> Vec<(String, JexValue)> {
+        self.fields.borrow().fields.clone()
+    }
+    /// Names of own fields in the order they were first set
+    pub fn field_names(&self) -> Vec<String> {
+        let fields = self.fields.borrow();
+        fields.fields.iter().map(|(name, _)| name.clone()).collect()
     }
+    pub fn field_values(&self) -> Vec<JexValue> {
+        let fields = self.fields.borrow();
+        fields
+            .fields
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+    /// Replaces the value of an existing field in place or adds a new field to the end
     pub fn put_field(&self, name: String, value: JexValue) {
-        self.fields.borrow_mut().insert(name, value);
+        let mut fields = self.fields.borrow_mut();
+        if let Some(index) = fields.indices.get(&name).copied() {
+            fields.fields[index].1 = value;
+        } else {
+            let index = fields.fields.len();
+            fields.indices.insert(name.clone(), index);
+            fields.fields.push((name, value));
+        }
+    }
+    /// Removes the own field and returns its value, fields of prototypes are not removed
+    pub fn remove_field(&self, name: &str) -> Option<JexValue> {
+        let mut fields = self.fields.borrow_mut();
+        let removed_index = fields.indices.remove(name)?;
+        let (_, value) = fields.fields.remove(removed_index);
+        for index in fields.indices.values_mut() {
+            if *index > removed_index {
+                *index -= 1;
+            }
+        }
+        Some(value)
+    }
+    pub fn prototype(&self) -> Option<Rc<JexInstance>> {
+        self.prototype.borrow().clone()
+    }
+    pub fn set_prototype(&self, prototype: Option<Rc<JexInstance>>) {
+        *self.prototype.borrow_mut() = prototype;
+    }
+    /// Removes all fields and the prototype
+    pub fn clear(&self) {
+        let fields = std::mem::take(&mut *self.fields.borrow_mut());
+        let prototype = self.prototype.borrow_mut().take();
+        // values are dropped after the borrows end because dropping them may drop other objects
+        drop((fields, prototype));
+    }
+    /// Finds the field in the instance or in its chain of prototypes, fails if the chain is a cycle
+    pub fn find_field(&self, name: &str) -> Result<Option<JexValue>, PrototypeCycle> {
+        if let Some(field) = self.get_field(name) {
+            return Ok(Some(field));
+        }
+        let mut visited: Vec<*const JexInstance> = vec![self];
+        let mut current = self.prototype();
+        while let Some(prototype) = current {
+            if visited.contains(&Rc::as_ptr(&prototype)) {
+                return Err(PrototypeCycle(name.to_string()));
+            }
+            if let Some(field) = prototype.get_field(name) {
+                return Ok(Some(field));
+            }
+            visited.push(Rc::as_ptr(&prototype));
+            current = prototype.prototype();
+        }
+        Ok(None)
+    }
+}
+
+impl JexArray {
+    pub fn new(elements: Vec<JexValue>) -> JexArray {
+        JexArray {
+            elements: RefCell::new(elements),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.elements.borrow().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.elements.borrow().is_empty()
+    }
+    pub fn get(&self, index: usize) -> Option<JexValue> {
+        self.elements.borrow().get(index).cloned()
+    }
+    /// Replaces the element at `index`, returns `false` if the index is out of bounds
+    pub fn set(&self, index: usize, value: JexValue) -> bool {
+        match self.elements.borrow_mut().get_mut(index) {
+            Some(element) => {
+                *element = value;
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn push(&self, value: JexValue) {
+        self.elements.borrow_mut().push(value);
+    }
+    pub fn elements(&self) -> Vec<JexValue> {
+        self.elements.borrow().clone()
+    }
+    pub fn clear(&self) {
+        let elements = std::mem::take(&mut *self.elements.borrow_mut());
+        drop(elements);
+    }
+}
+
+impl MapKey {
+    fn new(key: &JexValue) -> Result<MapKey, InvalidMapKey> {
+        if key.is_valid_map_key() {
+            Ok(MapKey(key.clone()))
+        } else {
+            Err(InvalidMapKey::new(key))
+        }
+    }
+}
+
+impl JexMap {
+    pub fn new() -> JexMap {
+        JexMap {
+            entries: RefCell::new(MapEntries::default()),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.entries.borrow().entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().entries.is_empty()
+    }
+    pub fn get(&self, key: &JexValue) -> Result<Option<JexValue>, InvalidMapKey> {
+        let key = MapKey::new(key)?;
+        let entries = self.entries.borrow();
+        Ok(entries
+            .indices
+            .get(&key)
+            .map(|index| entries.entries[*index].1.clone()))
+    }
+    pub fn contains_key(&self, key: &JexValue) -> Result<bool, InvalidMapKey> {
+        let key = MapKey::new(key)?;
+        Ok(self.entries.borrow().indices.contains_key(&key))
+    }
+    /// Replaces the value of an existing key in place or adds a new entry to the end
+    pub fn insert(&self, key: JexValue, value: JexValue) -> Result<(), InvalidMapKey> {
+        let map_key = MapKey::new(&key)?;
+        let mut entries = self.entries.borrow_mut();
+        if let Some(index) = entries.indices.get(&map_key).copied() {
+            entries.entries[index].1 = value;
+        } else {
+            let index = entries.entries.len();
+            entries.indices.insert(map_key, index);
+            entries.entries.push((key, value));
+        }
+        Ok(())
+    }
+    /// Removes the entry and returns its value, the order of the other entries is preserved
+    pub fn remove(&self, key: &JexValue) -> Result<Option<JexValue>, InvalidMapKey> {
+        let key = MapKey::new(key)?;
+        let mut entries = self.entries.borrow_mut();
+        let removed_index = match entries.indices.remove(&key) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let (_, value) = entries.entries.remove(removed_index);
+        for index in entries.indices.values_mut() {
+            if *index > removed_index {
+                *index -= 1;
+            }
+        }
+        Ok(Some(value))
+    }
+    /// Keys in the order they were inserted
+    pub fn keys(&self) -> Vec<JexValue> {
+        let entries = self.entries.borrow();
+        entries.entries.iter().map(|(key, _)| key.clone()).collect()
+    }
+    /// Entries in the order their keys were inserted
+    pub fn entries(&self) -> Vec<(JexValue, JexValue)> {
+        self.entries.borrow().entries.clone()
+    }
+    pub fn clear(&self) {
+        let entries = std::mem::take(&mut *self.entries.borrow_mut());
+        drop(entries);
+    }
+}
+
+impl Default for JexMap {
+    fn default() -> Self {
+        JexMap::new()
     }
 }
 
@@ -139,19 +608,52 @@ impl PartialEq for JexValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (JexValue::Int(i1), JexValue::Int(i2)) => i1 == i2,
+            (JexValue::Float(f1), JexValue::Float(f2)) => f1 == f2,
+            (JexValue::Int(i), JexValue::Float(f)) | (JexValue::Float(f), JexValue::Int(i)) => {
+                f64::from(*i) == *f
+            }
             (JexValue::Bool(b1), JexValue::Bool(b2)) => b1 == b2,
             (JexValue::Null(_), JexValue::Null(_)) => true,
             (JexValue::Object(o1), JexValue::Object(o2)) => o1 == o2,
             (JexValue::Function(f1), JexValue::Function(f2)) => f1 == f2,
-            (JexValue::Instance(i1), JexValue::Instance(i2)) => {
-                &**i1 as *const JexInstance == &**i2 as *const JexInstance
-            }
+            (JexValue::Instance(i1), JexValue::Instance(i2)) => Rc::ptr_eq(i1, i2),
+            (JexValue::Array(a1), JexValue::Array(a2)) => Rc::ptr_eq(a1, a2),
+            (JexValue::Map(m1), JexValue::Map(m2)) => Rc::ptr_eq(m1, m2),
+            (JexValue::Class(c1), JexValue::Class(c2)) => Rc::ptr_eq(c1, c2),
+            (JexValue::Cell(c1), JexValue::Cell(c2)) => Rc::ptr_eq(c1, c2),
             _ => false,
         }
     }
+}
 
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
+/// Values that are equal have equal hashes:
+/// an integral float is hashed like the int it is equal to
+/// and instances, arrays and maps are hashed by their identity
+impl Hash for JexValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            JexValue::Null(_) => state.write_u8(0),
+            JexValue::Int(int) => int.hash(state),
+            JexValue::Float(float) => {
+                let int = *float as i32;
+                if f64::from(int) == *float {
+                    int.hash(state)
+                } else {
+                    float.to_bits().hash(state)
+                }
+            }
+            JexValue::Bool(bool) => bool.hash(state),
+            JexValue::Object(obj) => {
+                let JexObject::String(string) = &**obj;
+                string.hash(state)
+            }
+            JexValue::Function(func) => func.hash(state),
+            JexValue::Instance(instance) => Rc::as_ptr(instance).hash(state),
+            JexValue::Array(array) => Rc::as_ptr(array).hash(state),
+            JexValue::Map(map) => Rc::as_ptr(map).hash(state),
+            JexValue::Class(class) => Rc::as_ptr(class).hash(state),
+            JexValue::Cell(cell) => Rc::as_ptr(cell).hash(state),
+        }
     }
 }
 
@@ -159,11 +661,19 @@ impl Debug for JexValue {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             JexValue::Int(int) => write!(f, "{}", int),
+            JexValue::Float(float) => write!(f, "{}", float.to_output_string()),
             JexValue::Bool(bool) => write!(f, "{}", bool),
             JexValue::Null(_) => write!(f, "null"),
             JexValue::Function(func) => write!(f, "{}", func.to_output_string()),
             JexValue::Object(obj) => write!(f, "{:?}", &**obj),
-            JexValue::Instance(_) => write!(f, "object"),
+            JexValue::Instance(_) | JexValue::Array(_) | JexValue::Map(_) if f.alternate() => {
+                write!(f, "{}", to_pretty_string(self))
+            }
+            JexValue::Instance(instance) => write!(f, "{}", instance.to_output_string()),
+            JexValue::Array(array) => write!(f, "{}", array.to_output_string()),
+            JexValue::Map(map) => write!(f, "{}", map.to_output_string()),
+            JexValue::Class(class) => write!(f, "{}", class.to_output_string()),
+            JexValue::Cell(cell) => write!(f, "{:?}", cell.get()),
         }
     }
 }
@@ -178,7 +688,17 @@ impl Debug for JexObject {
 
 #[cfg(test)]
 mod tests {
-    use crate::values::values::JexValue;
+    use crate::values::to_output_string::{to_pretty_string, ToOutputString};
+    use crate::values::values::{JexClass, JexInstance, JexValue};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::rc::Rc;
+
+    fn hash(value: &JexValue) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
 
     #[test]
     fn cloned_strings_should_be_equal() {
@@ -199,6 +719,11 @@ mod tests {
         assert_ne!(str, str2);
     }
     #[test]
+    fn int_and_float_with_same_value_should_be_equal() {
+        assert_eq!(JexValue::Int(2), JexValue::Float(2.0));
+        assert_ne!(JexValue::Int(2), JexValue::Float(2.5));
+    }
+    #[test]
     fn two_empty_objects_should_not_be_equal() {
         let obj = JexValue::new_object();
         let obj2 = JexValue::new_object();
@@ -210,4 +735,191 @@ mod tests {
         let obj2 = obj.clone();
         assert_eq!(obj, obj2);
     }
+
+    #[test]
+    fn fields_should_be_found_in_prototypes() {
+        let object = JexInstance::new();
+        let parent = Rc::new(JexInstance::new());
+        let grandparent = Rc::new(JexInstance::new());
+        grandparent.put_field("a".to_string(), JexValue::Int(1));
+        parent.set_prototype(Some(grandparent));
+        object.set_prototype(Some(parent));
+        assert_eq!(Some(JexValue::Int(1)), object.find_field("a").unwrap());
+        assert_eq!(None, object.find_field("b").unwrap());
+    }
+
+    #[test]
+    fn prototype_cycles_should_be_detected() {
+        let first = Rc::new(JexInstance::new());
+        let second = Rc::new(JexInstance::new());
+        first.set_prototype(Some(second.clone()));
+        second.set_prototype(Some(first.clone()));
+        assert!(first.find_field("a").is_err());
+        first.put_field("a".to_string(), JexValue::Int(1));
+        assert_eq!(Some(JexValue::Int(1)), second.find_field("a").unwrap());
+    }
+
+    #[test]
+    fn arrays_should_be_equal_only_to_themselves() {
+        let array = JexValue::from_elements(vec![JexValue::Int(1)]);
+        assert_eq!(array, array.clone());
+        assert_ne!(array, JexValue::from_elements(vec![JexValue::Int(1)]));
+    }
+
+    #[test]
+    fn arrays_should_be_printed_with_quoted_strings() {
+        let array = JexValue::from_elements(vec![
+            JexValue::Int(1),
+            JexValue::from_string("a".to_string()),
+            JexValue::from_elements(vec![]),
+        ]);
+        assert_eq!("[1, \"a\", []]", array.to_output_string());
+    }
+
+    #[test]
+    fn array_that_contains_itself_should_be_printed() {
+        let array = JexValue::from_elements(vec![JexValue::Int(1)]);
+        array.as_array().unwrap().push(array.clone());
+        assert_eq!("[1, [...]]", array.to_output_string());
+    }
+
+    #[test]
+    fn maps_should_be_printed_in_insertion_order() {
+        let map = JexValue::new_map();
+        let entries = map.as_map().unwrap();
+        entries
+            .insert(JexValue::from_string("k".to_string()), JexValue::Int(1))
+            .unwrap();
+        entries.insert(JexValue::null(), map.clone()).unwrap();
+        assert_eq!("{\"k\": 1, null: {...}}", map.to_output_string());
+    }
+
+    fn object(fields: Vec<(&str, JexValue)>) -> JexValue {
+        let object = JexValue::new_object();
+        for (name, value) in fields {
+            object
+                .as_instance()
+                .unwrap()
+                .put_field(name.to_string(), value);
+        }
+        object
+    }
+
+    #[test]
+    fn objects_should_be_printed_with_fields_in_insertion_order() {
+        let object = object(vec![
+            ("name", JexValue::from_string("a".to_string())),
+            ("age", JexValue::Int(3)),
+            ("tags", JexValue::from_elements(vec![object(vec![])])),
+        ]);
+        assert_eq!(
+            "{name: \"a\", age: 3, tags: [{}]}",
+            object.to_output_string()
+        );
+        assert_eq!(object.to_output_string(), format!("{:?}", object));
+    }
+
+    #[test]
+    fn objects_of_classes_should_be_printed_with_class_name() {
+        let class = Rc::new(JexClass::new("Point".to_string()));
+        let point = JexValue::new_instance_of(class);
+        point
+            .as_instance()
+            .unwrap()
+            .put_field("x".to_string(), JexValue::Int(1));
+        assert_eq!("Point {x: 1}", point.to_output_string());
+    }
+
+    #[test]
+    fn cycles_through_objects_should_be_printed_as_back_references() {
+        let first = object(vec![("id", JexValue::Int(1))]);
+        let second = object(vec![("first", first.clone())]);
+        let array = JexValue::from_elements(vec![second.clone()]);
+        first
+            .as_instance()
+            .unwrap()
+            .put_field("items".to_string(), array);
+        assert_eq!("{id: 1, items: [{first: {...}}]}", first.to_output_string());
+        first
+            .as_instance()
+            .unwrap()
+            .put_field("self".to_string(), first.clone());
+        assert_eq!(
+            "{id: 1, items: [{first: {...}}], self: {...}}",
+            first.to_output_string()
+        );
+        // break the cycles so that the objects are freed
+        first.as_instance().unwrap().clear();
+    }
+
+    #[test]
+    fn pretty_string_should_indent_nested_values() {
+        let inner = object(vec![
+            ("name", JexValue::from_string("b".to_string())),
+            ("empty", object(vec![])),
+        ]);
+        let outer = object(vec![
+            ("child", inner),
+            ("list", JexValue::from_elements(vec![JexValue::Int(1)])),
+        ]);
+        let expected = "\
+{
+  child: {
+    name: \"b\",
+    empty: {}
+  },
+  list: [
+    1
+  ]
+}";
+        assert_eq!(expected, to_pretty_string(&outer));
+        assert_eq!(expected, format!("{:#?}", outer));
+        assert_eq!(
+            "a",
+            to_pretty_string(&JexValue::from_string("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn equal_values_should_have_equal_hashes() {
+        assert_eq!(hash(&JexValue::Int(2)), hash(&JexValue::Float(2.0)));
+        assert_eq!(
+            hash(&JexValue::from_string("a".to_string())),
+            hash(&JexValue::from_string("a".to_string()))
+        );
+        let array = JexValue::from_elements(vec![]);
+        assert_eq!(hash(&array), hash(&array.clone()));
+    }
+
+    #[test]
+    fn map_should_keep_insertion_order() {
+        let map = JexValue::new_map();
+        let map = map.as_map().unwrap();
+        for key in &[3, 1, 2] {
+            map.insert(JexValue::Int(*key), JexValue::null()).unwrap();
+        }
+        map.insert(JexValue::Int(1), JexValue::Bool(true)).unwrap();
+        map.remove(&JexValue::Int(3)).unwrap();
+        map.insert(JexValue::Int(3), JexValue::null()).unwrap();
+        assert_eq!(
+            vec![JexValue::Int(1), JexValue::Int(2), JexValue::Int(3)],
+            map.keys()
+        );
+        assert_eq!(
+            Some(JexValue::Bool(true)),
+            map.get(&JexValue::Int(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn only_ints_strings_booleans_and_null_should_be_map_keys() {
+        let map = JexValue::new_map();
+        let map = map.as_map().unwrap();
+        assert!(map.insert(JexValue::Float(1.0), JexValue::null()).is_err());
+        assert!(map.get(&JexValue::new_object()).is_err());
+        assert!(map
+            .insert(JexValue::from_string("a".to_string()), JexValue::null())
+            .is_ok());
+        assert!(map.contains_key(&JexValue::null()).is_ok());
+    }
 }
@@ -1,22 +1,37 @@
+#![allow(dead_code)]
+
 pub mod code;
 
 pub mod run_jex {
     use crate::run::code::{TestChunk, TestInstruction};
     use extendable_vm::{Chunk, Code};
-    use jex_vm::build_jex_machine;
+    use jex_vm::build_jex_machine_with_options;
+    use jex_vm::code::assembler::assemble_bytecode;
     use jex_vm::code::bytecode_constants::JexConstant;
+    use jex_vm::options::JexMachineOptions;
+    use jex_vm::runtime::{run_jex_machine, DebugSymbols, JexRuntimeError};
     use jex_vm::values::values::JexValue;
 
     pub fn run_chunks(chunks: Vec<TestChunk>) -> Option<JexValue> {
+        run_chunks_with_options(chunks, &JexMachineOptions::default())
+    }
+
+    fn compile_chunks(chunks: Vec<TestChunk>) -> Code<JexConstant> {
         let mut compiled_chunks: Vec<Chunk<JexConstant>> = vec![];
         for chunk in chunks {
             compiled_chunks.push(chunk.compile());
         }
-        let code = Code {
+        Code {
             chunks: compiled_chunks,
-        };
+        }
+    }
 
-        let mut machine = build_jex_machine(&code);
+    pub fn run_chunks_with_options(
+        chunks: Vec<TestChunk>,
+        options: &JexMachineOptions,
+    ) -> Option<JexValue> {
+        let code = compile_chunks(chunks);
+        let mut machine = build_jex_machine_with_options(&code, options);
 
         let finished_gracefully = machine.start();
         if !finished_gracefully {
@@ -29,6 +44,34 @@ pub mod run_jex {
         run_chunks(vec![chunk])
     }
 
+    /// Runs the chunk and returns the exception that stopped the machine instead of panicking
+    pub fn try_run_chunk(chunk: TestChunk) -> Result<Option<JexValue>, JexRuntimeError> {
+        let options = JexMachineOptions::default();
+        let code = compile_chunks(vec![chunk]);
+        let mut machine = build_jex_machine_with_options(&code, &options);
+        let symbols = DebugSymbols {
+            debug_info: None,
+            local_names: None,
+        };
+        run_jex_machine(&mut machine, &options, symbols)?;
+        Ok(machine.peek_operand().ok().cloned())
+    }
+
+    /// Assembles and runs the program, returns the value on top of the stack
+    /// or the exception that stopped the machine
+    pub fn run_source(source: &str) -> Result<JexValue, JexRuntimeError> {
+        let options = JexMachineOptions::default();
+        let bytecode = assemble_bytecode(source).unwrap();
+        let mut machine = build_jex_machine_with_options(&bytecode.code, &options);
+        run_jex_machine(&mut machine, &options, DebugSymbols::of(&bytecode))?;
+        Ok(machine.peek_operand().unwrap().clone())
+    }
+
+    /// Same as `run_source` for the instructions of the script chunk
+    pub fn run_script(script: &str) -> Result<JexValue, JexRuntimeError> {
+        run_source(&format!(".script\n{}", script))
+    }
+
     pub fn run_instructions(instructions: Vec<TestInstruction>) -> Option<JexValue> {
         let chunk = TestChunk {
             constants: vec![],
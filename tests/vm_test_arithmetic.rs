@@ -0,0 +1,121 @@
+use jex_vm::code::bytecode_constants::JexConstant;
+use jex_vm::instructions::op_codes::JexOpCode;
+use jex_vm::instructions::operator_implementations::ArithmeticMode;
+use jex_vm::options::JexMachineOptions;
+use jex_vm::values::values::JexValue;
+use run::code::{TestChunk, TestInstruction};
+use run::run_jex::{run_chunk, run_chunks_with_options, try_run_chunk};
+
+mod run;
+
+fn max_int_plus_one() -> TestChunk {
+    TestChunk {
+        constants: vec![JexConstant::Int(i32::MAX), JexConstant::Int(1)],
+        instructions: vec![
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![0],
+            },
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![1],
+            },
+            TestInstruction::new(JexOpCode::Add),
+        ],
+    }
+}
+
+fn run_in_mode(chunk: TestChunk, arithmetic_mode: ArithmeticMode) -> Option<JexValue> {
+    run_chunks_with_options(
+        vec![chunk],
+        &JexMachineOptions {
+            arithmetic_mode,
+            ..JexMachineOptions::default()
+        },
+    )
+}
+
+#[test]
+fn it_should_raise_exception_on_division_by_zero() {
+    let error = try_run_chunk(TestChunk {
+        constants: vec![JexConstant::Int(1), JexConstant::Int(0)],
+        instructions: vec![
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![0],
+            },
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![1],
+            },
+            TestInstruction::new(JexOpCode::Divide),
+        ],
+    })
+    .unwrap_err();
+    assert_eq!("DivisionByZero", error.exception.name);
+}
+
+#[test]
+fn it_should_raise_exception_on_overflow_by_default() {
+    let error = try_run_chunk(max_int_plus_one()).unwrap_err();
+    assert_eq!("IntegerOverflow", error.exception.name);
+}
+
+#[test]
+fn it_should_wrap_on_overflow_in_wrapping_mode() {
+    let result = run_in_mode(max_int_plus_one(), ArithmeticMode::Wrapping);
+    assert_eq!(JexValue::Int(i32::MIN), result.unwrap());
+}
+
+#[test]
+fn it_should_saturate_on_overflow_in_saturating_mode() {
+    let result = run_in_mode(max_int_plus_one(), ArithmeticMode::Saturating);
+    assert_eq!(JexValue::Int(i32::MAX), result.unwrap());
+}
+
+fn int_operation(left: i32, right: i32, op_code: JexOpCode) -> TestChunk {
+    TestChunk {
+        constants: vec![JexConstant::Int(left), JexConstant::Int(right)],
+        instructions: vec![
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![0],
+            },
+            TestInstruction {
+                op_code: JexOpCode::Constant,
+                args: vec![1],
+            },
+            TestInstruction::new(op_code),
+        ],
+    }
+}
+
+#[test]
+fn it_should_raise_exception_on_modulo_by_zero() {
+    let error = try_run_chunk(int_operation(1, 0, JexOpCode::Modulo)).unwrap_err();
+    assert_eq!("DivisionByZero", error.exception.name);
+}
+
+#[test]
+fn it_should_compute_modulo_and_bitwise_operators() {
+    let cases = [
+        (-7, 3, JexOpCode::Modulo, -1),
+        (0b1100, 0b1010, JexOpCode::BitAnd, 0b1000),
+        (0b1100, 0b1010, JexOpCode::BitOr, 0b1110),
+        (0b1100, 0b1010, JexOpCode::BitXor, 0b0110),
+        (1, 4, JexOpCode::ShiftLeft, 16),
+        (-16, 40, JexOpCode::ShiftRight, -1),
+    ];
+    for (left, right, op_code, expected) in cases.iter() {
+        let result = run_chunk(int_operation(*left, *right, *op_code));
+        assert_eq!(Some(JexValue::Int(*expected)), result, "{:?}", op_code);
+    }
+}
+
+#[test]
+fn modulo_should_behave_the_same_in_every_mode() {
+    for mode in [ArithmeticMode::Wrapping, ArithmeticMode::Saturating].iter() {
+        let result = run_in_mode(int_operation(i32::MIN, -1, JexOpCode::Modulo), *mode);
+        assert_eq!(Some(JexValue::Int(0)), result);
+    }
+}
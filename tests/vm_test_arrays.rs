@@ -0,0 +1,100 @@
+use jex_vm::code::bytecode_constants::JexConstant;
+use jex_vm::instructions::op_codes::JexOpCode;
+use jex_vm::values::values::JexValue;
+use run::code::{TestChunk, TestInstruction};
+use run::run_jex::{run_chunk, run_instructions};
+
+mod run;
+
+fn push_ints(ints: &[u8]) -> Vec<TestInstruction> {
+    ints.iter()
+        .map(|int| TestInstruction {
+            op_code: JexOpCode::Constant,
+            args: vec![*int],
+        })
+        .collect()
+}
+
+fn run_with_ints(instructions: Vec<TestInstruction>) -> Option<JexValue> {
+    run_chunk(TestChunk {
+        constants: (0..10).map(JexConstant::Int).collect(),
+        instructions,
+    })
+}
+
+#[test]
+fn it_should_create_empty_array() {
+    let result = run_instructions(vec![TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![0],
+    }]);
+    assert!(result.unwrap().as_array().unwrap().is_empty());
+}
+
+#[test]
+fn it_should_create_array_from_stack_values_in_order() {
+    let mut instructions = push_ints(&[1, 2, 3]);
+    instructions.push(TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![3],
+    });
+    let result = run_with_ints(instructions).unwrap();
+    assert_eq!(
+        vec![JexValue::Int(1), JexValue::Int(2), JexValue::Int(3)],
+        result.as_array().unwrap().elements()
+    );
+}
+
+#[test]
+fn it_should_get_element_by_index() {
+    let mut instructions = push_ints(&[5, 6]);
+    instructions.push(TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![2],
+    });
+    instructions.extend(push_ints(&[1]));
+    instructions.push(TestInstruction::new(JexOpCode::IndexGet));
+    assert_eq!(JexValue::Int(6), run_with_ints(instructions).unwrap());
+}
+
+#[test]
+fn it_should_set_element_by_index_and_keep_array_on_stack() {
+    let mut instructions = push_ints(&[5, 6]);
+    instructions.push(TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![2],
+    });
+    instructions.extend(push_ints(&[0, 9]));
+    instructions.push(TestInstruction::new(JexOpCode::IndexSet));
+    let result = run_with_ints(instructions).unwrap();
+    assert_eq!(
+        vec![JexValue::Int(9), JexValue::Int(6)],
+        result.as_array().unwrap().elements()
+    );
+}
+
+#[test]
+fn it_should_push_elements_and_count_them() {
+    let mut instructions = vec![TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![0],
+    }];
+    instructions.extend(push_ints(&[7]));
+    instructions.push(TestInstruction::new(JexOpCode::ArrayPush));
+    instructions.extend(push_ints(&[8]));
+    instructions.push(TestInstruction::new(JexOpCode::ArrayPush));
+    instructions.push(TestInstruction::new(JexOpCode::Len));
+    assert_eq!(JexValue::Int(2), run_with_ints(instructions).unwrap());
+}
+
+#[test]
+#[should_panic]
+fn it_should_fail_on_out_of_bounds_index() {
+    let mut instructions = vec![TestInstruction {
+        op_code: JexOpCode::NewArray,
+        args: vec![0],
+    }];
+    instructions.extend(push_ints(&[0]));
+    instructions.push(TestInstruction::new(JexOpCode::IndexGet));
+    run_with_ints(instructions);
+}
@@ -6,6 +6,7 @@ use run::run_jex::run_chunks;
 mod run;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_jump_to_another_chunk() {
     let result = run_chunks(vec![
         TestChunk {
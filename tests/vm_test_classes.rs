@@ -0,0 +1,124 @@
+use jex_vm::runtime::JexRuntimeError;
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_source;
+
+mod run;
+
+/// Defines class `Point` in slot 1 and its instance `Point(2, 3)` in slot 2
+const POINT: &str = r#"
+.script
+    CLASS Point
+    CONSTANT @init
+    METHOD init
+    CONSTANT @sum
+    METHOD sum
+    GET_LOCAL 1
+    CONSTANT 2
+    CONSTANT 3
+    CALL 2
+
+.function init 2
+    GET_LOCAL 0
+    GET_LOCAL 1
+    SET_FIELD x
+    GET_LOCAL 2
+    SET_FIELD y
+    RETURN
+
+.function sum 0
+    GET_LOCAL 0
+    GET_FIELD x
+    GET_LOCAL 0
+    GET_FIELD y
+    ADD
+    RETURN
+"#;
+
+fn run_with_point(script: &str) -> Result<JexValue, JexRuntimeError> {
+    let source = POINT.replacen(".function init", &format!("{}\n.function init", script), 1);
+    run_source(&source)
+}
+
+#[test]
+fn calling_class_should_create_instance_with_init() {
+    let result = run_with_point("").unwrap();
+    let instance = result.as_instance().unwrap();
+    assert_eq!(Some(JexValue::Int(2)), instance.get_field("x"));
+    assert_eq!(Some(JexValue::Int(3)), instance.get_field("y"));
+}
+
+#[test]
+fn invoke_should_call_method_with_receiver() {
+    let result = run_with_point("    INVOKE sum 0");
+    assert_eq!(JexValue::Int(5), result.unwrap());
+}
+
+#[test]
+fn invoke_should_call_closure_stored_in_field() {
+    let source = r#"
+.script
+    CONSTANT 7
+    NEW_INSTANCE
+    CONSTANT @get
+    CLOSURE local 1
+    SET_FIELD get
+    INVOKE get 0
+
+.function get 0
+    GET_UPVALUE 0
+    RETURN
+"#;
+    assert_eq!(JexValue::Int(7), run_source(source).unwrap());
+}
+
+#[test]
+fn get_field_should_return_bound_method() {
+    let script = "
+    GET_LOCAL 2
+    GET_FIELD sum
+    CALL 0";
+    assert_eq!(JexValue::Int(5), run_with_point(script).unwrap());
+}
+
+#[test]
+fn fields_should_shadow_methods() {
+    let script = "
+    CONSTANT 10
+    SET_FIELD sum
+    GET_FIELD sum";
+    assert_eq!(JexValue::Int(10), run_with_point(script).unwrap());
+}
+
+#[test]
+fn invoking_missing_method_should_fail() {
+    let error = run_with_point("    INVOKE area 0").unwrap_err();
+    assert_eq!("FieldNotFound", error.exception.name);
+}
+
+#[test]
+fn calling_class_should_return_value_returned_by_init() {
+    let source = "
+.script
+    CLASS Empty
+    CONSTANT @init
+    METHOD init
+    CALL 0
+
+.function init 0
+    NULL
+    RETURN";
+    assert_eq!(JexValue::null(), run_source(source).unwrap());
+}
+
+#[test]
+fn class_without_init_should_not_receive_arguments() {
+    let source = "
+.script
+    CLASS Empty
+    CONSTANT 1
+    CALL 1";
+    assert_eq!(
+        "TypeException",
+        run_source(source).unwrap_err().exception.name
+    );
+}
@@ -0,0 +1,96 @@
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_source;
+
+mod run;
+
+#[test]
+fn captured_local_should_outlive_its_call_frame() {
+    let source = r#"
+.script
+    CONSTANT @make_counter
+    CALL 0
+    GET_LOCAL 1
+    CALL 0
+    POP
+    GET_LOCAL 1
+    CALL 0
+    POP
+    GET_LOCAL 1
+    CALL 0
+
+.function make_counter 0
+    CONSTANT 0
+    CONSTANT @increment
+    CLOSURE local 1
+    RETURN
+
+.function increment 0
+    GET_UPVALUE 0
+    CONSTANT 1
+    ADD
+    SET_UPVALUE 0
+    GET_UPVALUE 0
+    RETURN
+"#;
+    assert_eq!(JexValue::Int(3), run_source(source).unwrap());
+}
+
+#[test]
+fn closure_should_share_variable_with_enclosing_frame() {
+    let source = r#"
+.script
+    CONSTANT 1
+    CONSTANT @set_x
+    CLOSURE local 1
+    CONSTANT 5
+    CALL 1
+    POP
+    GET_LOCAL 1
+
+.function set_x 1
+    GET_LOCAL 1
+    SET_UPVALUE 0
+    NULL
+    RETURN
+"#;
+    assert_eq!(JexValue::Int(5), run_source(source).unwrap());
+}
+
+#[test]
+fn closure_should_capture_upvalues_of_enclosing_closure() {
+    let source = r#"
+.script
+    CONSTANT 7
+    CONSTANT @outer
+    CLOSURE local 1
+    CALL 0
+    CALL 0
+
+.function outer 0
+    CONSTANT @inner
+    CLOSURE upvalue 0
+    RETURN
+
+.function inner 0
+    GET_UPVALUE 0
+    RETURN
+"#;
+    assert_eq!(JexValue::Int(7), run_source(source).unwrap());
+}
+
+#[test]
+fn function_without_captures_should_not_have_upvalues() {
+    let source = r#"
+.script
+    CONSTANT @f
+    CALL 0
+
+.function f 0
+    GET_UPVALUE 0
+    RETURN
+"#;
+    assert_eq!(
+        "UpvalueNotFound",
+        run_source(source).unwrap_err().exception.name
+    );
+}
@@ -0,0 +1,139 @@
+use jex_vm::values::to_output_string::ToOutputString;
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_script;
+
+mod run;
+
+#[test]
+fn has_field_should_check_fields_of_object() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD x
+    GET_LOCAL 1
+    HAS_FIELD x
+    GET_LOCAL 1
+    HAS_FIELD y
+    NEW_ARRAY 2"#;
+    assert_eq!(
+        "[true, false]",
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn has_field_should_find_fields_of_prototypes_and_methods() {
+    let script = r#"
+    NEW_INSTANCE
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD x
+    SET_PROTOTYPE
+    HAS_FIELD x"#;
+    assert_eq!(JexValue::Bool(true), run_script(script).unwrap());
+
+    let script = r#"
+    CLASS Point
+    CONSTANT @get
+    METHOD get
+    CALL 0
+    HAS_FIELD get
+
+.function get 0
+    NULL
+    RETURN"#;
+    assert_eq!(JexValue::Bool(true), run_script(script).unwrap());
+}
+
+#[test]
+fn delete_field_should_remove_own_field() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD x
+    CONSTANT 2
+    SET_FIELD y
+    DELETE_FIELD x
+    DELETE_FIELD z"#;
+    let result = run_script(script).unwrap();
+    let instance = result.as_instance().unwrap();
+    assert_eq!(None, instance.get_field("x"));
+    assert_eq!(Some(JexValue::Int(2)), instance.get_field("y"));
+}
+
+#[test]
+fn delete_field_should_keep_field_of_prototype() {
+    let script = r#"
+    NEW_INSTANCE
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD x
+    SET_PROTOTYPE
+    DELETE_FIELD x
+    GET_FIELD x"#;
+    assert_eq!(JexValue::Int(1), run_script(script).unwrap());
+}
+
+#[test]
+fn get_field_or_null_should_return_null_for_missing_field() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD x
+    GET_LOCAL 1
+    GET_FIELD_OR_NULL x
+    GET_LOCAL 1
+    GET_FIELD_OR_NULL y
+    NEW_ARRAY 2"#;
+    assert_eq!("[1, null]", run_script(script).unwrap().to_output_string());
+}
+
+#[test]
+fn dynamic_fields_should_be_set_and_read_by_name_from_stack() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT "x"
+    CONSTANT "y"
+    ADD
+    CONSTANT 5
+    SET_FIELD_DYNAMIC
+    GET_LOCAL 1
+    GET_FIELD xy
+    GET_LOCAL 1
+    CONSTANT "xy"
+    GET_FIELD_DYNAMIC
+    NEW_ARRAY 2"#;
+    assert_eq!("[5, 5]", run_script(script).unwrap().to_output_string());
+}
+
+// exceptions
+
+#[test]
+fn get_field_dynamic_should_raise_field_not_found() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT "x"
+    GET_FIELD_DYNAMIC"#;
+    let error = run_script(script).unwrap_err();
+    assert_eq!("FieldNotFound", error.exception.name);
+}
+
+#[test]
+fn dynamic_field_name_should_be_string() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT 1
+    TRUE
+    SET_FIELD_DYNAMIC"#;
+    let error = run_script(script).unwrap_err();
+    assert_eq!("TypeException", error.exception.name);
+}
+
+#[test]
+fn has_field_of_non_object_should_raise_exception() {
+    let script = r#"
+    CONSTANT 1
+    HAS_FIELD x"#;
+    let error = run_script(script).unwrap_err();
+    assert_eq!("NotObjectException", error.exception.name);
+}
@@ -0,0 +1,133 @@
+use jex_vm::code::assembler::assemble_bytecode;
+use jex_vm::heap::{GcOptions, GcStats, JexHeap};
+use jex_vm::options::JexMachineOptions;
+use jex_vm::runtime::{run_jex_machine_in_heap, DebugSymbols};
+use jex_vm::values::values::{JexInstance, JexValue, NativeArity};
+use jex_vm::{build_jex_machine, define_native};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+type Remembered = Rc<RefCell<Vec<Weak<JexInstance>>>>;
+
+/// Runs the script with a native `remember(instance)` that keeps weak references to the instances
+fn run(script: &str, gc: GcOptions) -> (JexValue, GcStats, Remembered) {
+    let bytecode = assemble_bytecode(&format!(".script\n{}", script)).unwrap();
+    let mut machine = build_jex_machine(&bytecode.code);
+    let remembered: Remembered = Rc::new(RefCell::new(vec![]));
+    let native_remembered = remembered.clone();
+    define_native(
+        &mut machine,
+        "remember",
+        NativeArity::Fixed(1),
+        move |_, args| {
+            if let JexValue::Instance(instance) = &args[0] {
+                native_remembered.borrow_mut().push(Rc::downgrade(instance));
+            }
+            Ok(args[0].clone())
+        },
+    );
+    let options = JexMachineOptions {
+        gc,
+        ..JexMachineOptions::default()
+    };
+    let heap = JexHeap::new(gc);
+    run_jex_machine_in_heap(&mut machine, &options, DebugSymbols::of(&bytecode), &heap).unwrap();
+    let result = machine.peek_operand().unwrap().clone();
+    (result, heap.stats(), remembered)
+}
+
+fn gc_with_threshold(threshold: usize) -> GcOptions {
+    GcOptions {
+        threshold,
+        ..GcOptions::default()
+    }
+}
+
+/// Creates an instance whose field `self` points at the instance and drops it
+const SELF_CYCLE: &str = "
+    GET_GLOBAL remember
+    NEW_INSTANCE
+    CALL 1
+    GET_LOCAL 1
+    SET_FIELD self
+    POP
+";
+
+fn live(remembered: &Remembered) -> usize {
+    remembered
+        .borrow()
+        .iter()
+        .filter(|instance| instance.upgrade().is_some())
+        .count()
+}
+
+#[test]
+fn unreachable_self_cycles_should_be_collected() {
+    let script = format!("{}\n    NULL", SELF_CYCLE.repeat(10));
+    let (_, stats, remembered) = run(&script, gc_with_threshold(4));
+    assert_eq!(10, remembered.borrow().len());
+    assert!(stats.collections > 0);
+    assert!(stats.collected > 0);
+    // at most the objects allocated after the last collection remain
+    assert!(live(&remembered) < 4);
+}
+
+#[test]
+fn objects_pointing_at_each_other_should_be_collected() {
+    let script = "
+    GET_GLOBAL remember
+    NEW_INSTANCE
+    CALL 1
+    GET_GLOBAL remember
+    NEW_INSTANCE
+    CALL 1
+    GET_LOCAL 1
+    SET_FIELD other
+    SET_FIELD other
+    POP
+    NEW_INSTANCE
+    NULL";
+    let (_, stats, remembered) = run(script, gc_with_threshold(3));
+    assert_eq!(1, stats.collections);
+    assert_eq!(2, stats.collected);
+    assert_eq!(0, live(&remembered));
+}
+
+#[test]
+fn cycles_reachable_from_stack_and_globals_should_survive() {
+    let script = "
+    GET_GLOBAL remember
+    NEW_INSTANCE
+    CALL 1
+    GET_LOCAL 1
+    SET_FIELD self
+    GET_GLOBAL remember
+    NEW_INSTANCE
+    CALL 1
+    GET_LOCAL 2
+    SET_FIELD self
+    DEFINE_GLOBAL kept
+    NEW_INSTANCE
+    POP
+    GET_GLOBAL kept
+    GET_FIELD self
+    POP";
+    let (result, stats, remembered) = run(script, gc_with_threshold(1));
+    assert!(stats.collections > 0);
+    assert_eq!(0, stats.collected);
+    assert_eq!(2, live(&remembered));
+    assert!(result.as_instance().unwrap().get_field("self").is_some());
+}
+
+#[test]
+fn disabled_collector_should_not_collect() {
+    let gc = GcOptions {
+        enabled: false,
+        threshold: 1,
+        ..GcOptions::default()
+    };
+    let (_, stats, remembered) = run(SELF_CYCLE, gc);
+    assert_eq!(0, stats.collections);
+    assert_eq!(1, stats.allocated);
+    assert_eq!(1, live(&remembered));
+}
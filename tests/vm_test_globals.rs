@@ -71,6 +71,7 @@ fn it_declare_and_get_calculated_global_variable() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn it_declare_and_get_bool_global_variable() {
     let result = run_chunk(TestChunk {
         constants: vec![JexConstant::from_str("varname"), JexConstant::Int(0)],
@@ -0,0 +1,52 @@
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_script;
+
+mod run;
+
+#[test]
+fn object_should_be_encoded_as_json() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT "bob"
+    SET_FIELD name
+    CONSTANT 3
+    CONSTANT 4
+    NEW_ARRAY 2
+    SET_FIELD scores
+    TO_JSON"#;
+    assert_eq!(
+        JexValue::from_string(r#"{"name":"bob","scores":[3,4]}"#.to_string()),
+        run_script(script).unwrap()
+    );
+}
+
+#[test]
+fn json_should_be_decoded_to_object() {
+    let script = r#"
+    CONSTANT "{\"point\": {\"x\": 1, \"y\": 2.5}}"
+    FROM_JSON
+    GET_FIELD point
+    GET_FIELD y"#;
+    assert_eq!(JexValue::Float(2.5), run_script(script).unwrap());
+}
+
+#[test]
+fn malformed_json_should_raise_exception_with_position() {
+    let script = r#"
+    CONSTANT "[1, 2"
+    FROM_JSON"#;
+    let error = run_script(script).unwrap_err();
+    assert_eq!("JsonParseException", error.exception.name);
+    assert!(error.exception.message.ends_with("at position 5"));
+}
+
+#[test]
+fn object_that_contains_itself_should_not_be_encoded() {
+    let script = "
+    NEW_INSTANCE
+    GET_LOCAL 1
+    SET_FIELD self
+    TO_JSON";
+    let error = run_script(script).unwrap_err();
+    assert_eq!("JsonEncodeException", error.exception.name);
+}
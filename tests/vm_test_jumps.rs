@@ -62,6 +62,7 @@ fn should_panic_if_jumped_before_code() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_skip_one_instruction_by_jumping_if_false() {
     let result = run_chunk(TestChunk {
         constants: vec![JexConstant::Int(0), JexConstant::Int(1)],
@@ -108,6 +109,7 @@ fn should_not_skip_one_instruction_by_jumping_if_true() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_jump_backward() {
     let result = run_chunk(TestChunk {
         constants: vec![JexConstant::Int(0), JexConstant::Int(1)],
@@ -0,0 +1,89 @@
+use jex_vm::code::bytecode_constants::JexConstant;
+use jex_vm::instructions::op_codes::JexOpCode;
+use jex_vm::values::values::JexValue;
+use run::code::{TestChunk, TestInstruction};
+use run::run_jex::{run_chunk, run_instructions};
+
+mod run;
+
+fn constant(i: u8) -> TestInstruction {
+    TestInstruction {
+        op_code: JexOpCode::Constant,
+        args: vec![i],
+    }
+}
+
+/// Constants are `0..10` followed by strings `"a"` and `"b"` at 10 and 11
+fn run_with_constants(instructions: Vec<TestInstruction>) -> Option<JexValue> {
+    let mut constants: Vec<JexConstant> = (0..10).map(JexConstant::Int).collect();
+    constants.push(JexConstant::String("a".to_string()));
+    constants.push(JexConstant::String("b".to_string()));
+    run_chunk(TestChunk {
+        constants,
+        instructions,
+    })
+}
+
+/// Creates a map and inserts `b -> 1` and `a -> 2`
+fn new_map_with_entries() -> Vec<TestInstruction> {
+    vec![
+        TestInstruction::new(JexOpCode::NewMap),
+        constant(11),
+        constant(1),
+        TestInstruction::new(JexOpCode::MapInsert),
+        constant(10),
+        constant(2),
+        TestInstruction::new(JexOpCode::MapInsert),
+    ]
+}
+
+#[test]
+fn it_should_create_empty_map() {
+    let result = run_instructions(vec![TestInstruction::new(JexOpCode::NewMap)]);
+    assert!(result.unwrap().as_map().unwrap().is_empty());
+}
+
+#[test]
+fn it_should_get_inserted_value() {
+    let mut instructions = new_map_with_entries();
+    instructions.push(constant(10));
+    instructions.push(TestInstruction::new(JexOpCode::MapGet));
+    assert_eq!(JexValue::Int(2), run_with_constants(instructions).unwrap());
+}
+
+#[test]
+fn it_should_list_keys_in_insertion_order() {
+    let mut instructions = new_map_with_entries();
+    instructions.push(TestInstruction::new(JexOpCode::MapKeys));
+    let result = run_with_constants(instructions).unwrap();
+    assert_eq!("[\"b\", \"a\"]", format!("{:?}", result));
+}
+
+#[test]
+fn it_should_remove_entries_and_count_them() {
+    let mut instructions = new_map_with_entries();
+    instructions.push(constant(11));
+    instructions.push(TestInstruction::new(JexOpCode::MapRemove));
+    instructions.push(TestInstruction::new(JexOpCode::Len));
+    assert_eq!(JexValue::Int(1), run_with_constants(instructions).unwrap());
+}
+
+#[test]
+fn it_should_check_if_key_is_contained() {
+    let mut instructions = new_map_with_entries();
+    instructions.push(constant(1));
+    instructions.push(TestInstruction::new(JexOpCode::MapContains));
+    assert_eq!(
+        JexValue::Bool(false),
+        run_with_constants(instructions).unwrap()
+    );
+}
+
+#[test]
+#[should_panic]
+fn it_should_fail_on_missing_key() {
+    let mut instructions = new_map_with_entries();
+    instructions.push(constant(0));
+    instructions.push(TestInstruction::new(JexOpCode::MapGet));
+    run_with_constants(instructions);
+}
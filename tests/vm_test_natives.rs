@@ -0,0 +1,110 @@
+use jex_vm::code::assembler::assemble_bytecode;
+use jex_vm::exceptions::runtime_exceptions::TypeException;
+use jex_vm::options::JexMachineOptions;
+use jex_vm::runtime::{run_jex_machine, DebugSymbols, JexRuntimeError};
+use jex_vm::types::JexMachine;
+use jex_vm::values::values::{JexValue, NativeArity};
+use jex_vm::{build_jex_machine, define_native};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn run(source: &str, define: impl Fn(&mut JexMachine)) -> Result<JexValue, JexRuntimeError> {
+    let bytecode = assemble_bytecode(source).unwrap();
+    let mut machine = build_jex_machine(&bytecode.code);
+    define(&mut machine);
+    run_jex_machine(
+        &mut machine,
+        &JexMachineOptions::default(),
+        DebugSymbols::of(&bytecode),
+    )?;
+    Ok(machine.peek_operand().unwrap().clone())
+}
+
+fn define_add_one(machine: &mut JexMachine) {
+    define_native(machine, "add_one", NativeArity::Fixed(1), |_, args| {
+        let int = args[0]
+            .as_int()
+            .ok_or_else(|| TypeException("add_one expects an Int".to_string()))?;
+        Ok(JexValue::Int(int + 1))
+    });
+}
+
+#[test]
+fn native_should_be_called_with_arguments() {
+    let source = "
+.script
+    GET_GLOBAL add_one
+    CONSTANT 41
+    CALL 1";
+    assert_eq!(JexValue::Int(42), run(source, define_add_one).unwrap());
+}
+
+#[test]
+fn variadic_native_should_receive_all_arguments_in_order() {
+    let source = "
+.script
+    GET_GLOBAL list
+    CONSTANT 1
+    CONSTANT 2
+    CONSTANT 3
+    CALL 3";
+    let result = run(source, |machine| {
+        define_native(machine, "list", NativeArity::Variadic, |_, args| {
+            Ok(JexValue::from_elements(args))
+        })
+    });
+    assert_eq!("[1, 2, 3]", format!("{:?}", result.unwrap()));
+}
+
+#[test]
+fn native_should_fail_on_wrong_number_of_arguments() {
+    let source = "
+.script
+    GET_GLOBAL add_one
+    CONSTANT 1
+    CONSTANT 2
+    CALL 2";
+    let error = run(source, define_add_one).unwrap_err();
+    assert_eq!("TypeException", error.exception.name);
+    assert_eq!(
+        "Function native function add_one(1 params) has 1 parameters but received 2",
+        error.exception.message
+    );
+}
+
+#[test]
+fn native_exceptions_should_stop_the_machine() {
+    let source = "
+.script
+    GET_GLOBAL add_one
+    TRUE
+    CALL 1";
+    let error = run(source, define_add_one).unwrap_err();
+    assert_eq!("add_one expects an Int", error.exception.message);
+}
+
+#[test]
+fn native_closures_should_keep_their_state() {
+    let printed = Rc::new(RefCell::new(vec![]));
+    let source = "
+.script
+    GET_GLOBAL record
+    CONSTANT 1
+    CALL 1
+    POP
+    GET_GLOBAL record
+    CONSTANT \"a\"
+    CALL 1";
+    let result = run(source, |machine| {
+        let printed = printed.clone();
+        define_native(machine, "record", NativeArity::Fixed(1), move |_, args| {
+            printed.borrow_mut().push(args[0].clone());
+            Ok(JexValue::null())
+        })
+    });
+    assert_eq!(JexValue::null(), result.unwrap());
+    assert_eq!(
+        vec![JexValue::Int(1), JexValue::from_string("a".to_string())],
+        *printed.borrow()
+    );
+}
@@ -134,3 +134,87 @@ fn it_should_panic_if_set_field_of_bool() {
         ],
     });
 }
+
+// prototypes
+
+#[test]
+fn it_should_get_field_of_prototype() {
+    let result = run_chunk(TestChunk {
+        constants: vec![JexConstant::String("field_name".to_string())],
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::True),
+            TestInstruction {
+                op_code: JexOpCode::SetField,
+                args: vec![0],
+            },
+            TestInstruction::new(JexOpCode::SetPrototype),
+            TestInstruction {
+                op_code: JexOpCode::GetField,
+                args: vec![0],
+            },
+        ],
+    });
+    assert_eq!(JexValue::Bool(true), result.unwrap());
+}
+
+#[test]
+fn it_should_set_field_of_receiver_and_not_of_prototype() {
+    let result = run_chunk(TestChunk {
+        constants: vec![JexConstant::String("field_name".to_string())],
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::True),
+            TestInstruction {
+                op_code: JexOpCode::SetField,
+                args: vec![0],
+            },
+            TestInstruction::new(JexOpCode::SetPrototype),
+            TestInstruction::new(JexOpCode::False),
+            TestInstruction {
+                op_code: JexOpCode::SetField,
+                args: vec![0],
+            },
+            TestInstruction::new(JexOpCode::GetPrototype),
+            TestInstruction {
+                op_code: JexOpCode::GetField,
+                args: vec![0],
+            },
+        ],
+    });
+    assert_eq!(JexValue::Bool(true), result.unwrap());
+}
+
+#[test]
+fn it_should_get_null_prototype_of_new_object() {
+    let result = run_chunk(TestChunk {
+        constants: vec![],
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::GetPrototype),
+        ],
+    });
+    assert_eq!(JexValue::null(), result.unwrap());
+}
+
+#[test]
+#[should_panic]
+fn it_should_panic_if_prototype_chain_is_a_cycle() {
+    run_chunk(TestChunk {
+        constants: vec![JexConstant::String("field_name".to_string())],
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction {
+                op_code: JexOpCode::GetLocal,
+                args: vec![1],
+            },
+            TestInstruction::new(JexOpCode::SetPrototype),
+            TestInstruction {
+                op_code: JexOpCode::GetField,
+                args: vec![0],
+            },
+        ],
+    });
+}
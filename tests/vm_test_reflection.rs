@@ -0,0 +1,75 @@
+use jex_vm::values::to_output_string::ToOutputString;
+use run::run_jex::run_script;
+
+mod run;
+
+#[test]
+fn field_names_should_list_own_fields_in_order_they_were_set() {
+    let script = r#"
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD y
+    CONSTANT 2
+    SET_FIELD x
+    CONSTANT 3
+    SET_FIELD y
+    GET_GLOBAL field_names
+    GET_LOCAL 1
+    CALL 1"#;
+    assert_eq!(
+        "[\"y\", \"x\"]",
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn copy_should_keep_class_of_object() {
+    let script = r#"
+    CLASS Point
+    CALL 0
+    CONSTANT 1
+    SET_FIELD x
+    GET_GLOBAL copy_object
+    GET_LOCAL 1
+    CALL 1
+    CONSTANT 2
+    SET_FIELD x
+    NEW_ARRAY 2"#;
+    assert_eq!(
+        "[Point {x: 1}, Point {x: 2}]",
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn merge_should_add_new_fields_after_fields_of_target() {
+    let script = r#"
+    GET_GLOBAL field_names
+    GET_GLOBAL merge_fields
+    NEW_INSTANCE
+    CONSTANT 1
+    SET_FIELD y
+    NEW_INSTANCE
+    CONSTANT 2
+    SET_FIELD x
+    CONSTANT 3
+    SET_FIELD y
+    CALL 2
+    CALL 1"#;
+    assert_eq!(
+        "[\"y\", \"x\"]",
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn field_count_of_non_object_should_raise_type_exception() {
+    let script = r#"
+    GET_GLOBAL field_count
+    NEW_MAP
+    CALL 1"#;
+    assert_eq!(
+        "TypeException",
+        run_script(script).unwrap_err().exception.name
+    );
+}
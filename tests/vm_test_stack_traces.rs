@@ -0,0 +1,105 @@
+use jex_vm::build_jex_machine;
+use jex_vm::code::assembler::assemble_bytecode;
+use jex_vm::options::JexMachineOptions;
+use jex_vm::runtime::{run_jex_machine, DebugSymbols, JexRuntimeError};
+
+fn run_for_error(source: &str) -> JexRuntimeError {
+    let bytecode = assemble_bytecode(source).unwrap();
+    let mut machine = build_jex_machine(&bytecode.code);
+    run_jex_machine(
+        &mut machine,
+        &JexMachineOptions::default(),
+        DebugSymbols::of(&bytecode),
+    )
+    .unwrap_err()
+}
+
+const FAILING_CALL: &str = r#"
+.script
+.file "main.jex"
+.line 1
+    CONSTANT @fail
+.line 2
+    CALL 0
+    PRINT
+
+.function fail 0
+.line 5
+    CONSTANT 1
+.line 6
+    TRUE
+    ADD
+    RETURN
+"#;
+
+#[test]
+fn stack_trace_should_contain_files_and_lines() {
+    let error = run_for_error(FAILING_CALL);
+    assert_eq!("OperatorUndefined", error.exception.name);
+    let frames: Vec<String> = error
+        .stack_trace
+        .iter()
+        .map(|frame| frame.to_string())
+        .collect();
+    assert_eq!(vec!["fail (main.jex:6)", "<script> (main.jex:2)"], frames);
+}
+
+#[test]
+fn stack_trace_without_debug_info_should_contain_offsets() {
+    let source = FAILING_CALL.replace(".file \"main.jex\"", "");
+    let source: String = source
+        .lines()
+        .filter(|line| !line.starts_with(".line"))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let error = run_for_error(&source);
+    let frames: Vec<String> = error
+        .stack_trace
+        .iter()
+        .map(|frame| frame.to_string())
+        .collect();
+    assert_eq!(vec!["fail (#1:3)", "<script> (#0:3)"], frames);
+}
+
+#[test]
+fn successful_run_should_not_return_an_error() {
+    let bytecode = assemble_bytecode(".script\n.line 1\n TRUE\n POP").unwrap();
+    let mut machine = build_jex_machine(&bytecode.code);
+    let result = run_jex_machine(
+        &mut machine,
+        &JexMachineOptions::default(),
+        DebugSymbols::of(&bytecode),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn stack_trace_should_contain_named_locals() {
+    let error = run_for_error(
+        r#"
+.script
+.file "main.jex"
+    CONSTANT @greet
+    CONSTANT "bob"
+    CALL 1
+    PRINT
+
+.function greet 1
+.local name 1
+.line 3
+    CONSTANT 3
+.local x 2
+.line 4
+    GET_LOCAL 1
+    GET_LOCAL 2
+    ADD
+    RETURN
+"#,
+    );
+    assert_eq!(
+        "[Runtime] OperatorUndefined: Binary operator plus is not defined for types String and Int\n\
+         \tat greet (main.jex:4): name = \"bob\", x = 3\n\
+         \tat <script> (main.jex)",
+        error.to_string()
+    );
+}
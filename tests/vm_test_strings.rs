@@ -0,0 +1,47 @@
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_script;
+
+mod run;
+
+#[test]
+fn string_globals_should_be_defined_by_default() {
+    let script = r#"
+    GET_GLOBAL to_upper
+    GET_GLOBAL substring
+    CONSTANT "привет, мир"
+    CONSTANT 8
+    CONSTANT 11
+    CALL 3
+    CALL 1"#;
+    assert_eq!(
+        JexValue::from_string("МИР".to_string()),
+        run_script(script).unwrap()
+    );
+}
+
+#[test]
+fn split_and_join_should_work_with_arrays() {
+    let script = r#"
+    GET_GLOBAL join
+    GET_GLOBAL split
+    CONSTANT " a b  c "
+    CONSTANT " "
+    CALL 2
+    CONSTANT "|"
+    CALL 2"#;
+    assert_eq!(
+        JexValue::from_string("|a|b||c|".to_string()),
+        run_script(script).unwrap()
+    );
+}
+
+#[test]
+fn bad_index_should_raise_exception() {
+    let script = r#"
+    GET_GLOBAL char_at
+    CONSTANT "abc"
+    CONSTANT 5
+    CALL 2"#;
+    let error = run_script(script).unwrap_err();
+    assert_eq!("StringIndexOutOfBounds", error.exception.name);
+}
@@ -0,0 +1,56 @@
+use jex_vm::values::to_output_string::ToOutputString;
+use jex_vm::values::values::JexValue;
+use run::run_jex::run_script;
+
+mod run;
+
+#[test]
+fn type_of_should_return_type_names() {
+    let script = r#"
+    CONSTANT 1
+    TYPE_OF
+    CONSTANT 1.5
+    TYPE_OF
+    TRUE
+    TYPE_OF
+    CONSTANT "a"
+    TYPE_OF
+    NULL
+    TYPE_OF
+    GET_GLOBAL trim
+    TYPE_OF
+    NEW_INSTANCE
+    TYPE_OF
+    NEW_MAP
+    TYPE_OF
+    NEW_ARRAY 8"#;
+    assert_eq!(
+        r#"["Int", "Float", "Boolean", "String", "null", "fn", "object", "Map"]"#,
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn is_type_should_compare_type_name_with_constant() {
+    let script = r#"
+    CONSTANT 1
+    IS_TYPE Int
+    CONSTANT "1"
+    IS_TYPE Int
+    NULL
+    IS_TYPE null
+    NEW_ARRAY 3"#;
+    assert_eq!(
+        "[true, false, true]",
+        run_script(script).unwrap().to_output_string()
+    );
+}
+
+#[test]
+fn is_type_should_check_result_of_parse_int() {
+    let script = r#"
+    CONSTANT "not a number"
+    PARSE_INT
+    IS_TYPE null"#;
+    assert_eq!(JexValue::Bool(true), run_script(script).unwrap());
+}
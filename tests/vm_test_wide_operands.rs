@@ -0,0 +1,216 @@
+use jex_vm::build_jex_machine;
+use jex_vm::code::assembler::{assemble, assemble_to_bytes};
+use jex_vm::code::bytecode_constants::JexConstant;
+use jex_vm::code::disassembler::disassemble;
+use jex_vm::code::loader::load_bytecode;
+use jex_vm::instructions::op_codes::JexOpCode;
+use jex_vm::values::to_output_string::ToOutputString;
+use jex_vm::values::values::JexValue;
+use run::code::{TestChunk, TestInstruction};
+use run::run_jex::{run_chunk, run_source};
+
+mod run;
+
+fn u16_args(value: u16) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+#[test]
+fn it_should_load_constant_with_index_above_255() {
+    let constants = (0..300).map(JexConstant::Int).collect();
+    let result = run_chunk(TestChunk {
+        constants,
+        instructions: vec![TestInstruction {
+            op_code: JexOpCode::ConstantLong,
+            args: u16_args(299),
+        }],
+    });
+    assert_eq!(JexValue::Int(299), result.unwrap());
+}
+
+#[test]
+fn it_should_jump_forward_by_more_than_255_bytes() {
+    let mut instructions = vec![
+        TestInstruction::new(JexOpCode::True),
+        TestInstruction {
+            op_code: JexOpCode::JumpForwardLong,
+            args: u16_args(300),
+        },
+    ];
+    for _ in 0..300 {
+        instructions.push(TestInstruction::new(JexOpCode::False));
+    }
+    let result = run_chunk(TestChunk {
+        constants: vec![],
+        instructions,
+    });
+    assert!(result.unwrap().as_bool().unwrap());
+}
+
+#[test]
+fn it_should_jump_backward_by_more_than_255_bytes() {
+    let mut instructions = vec![
+        TestInstruction {
+            // jump over the padding and the exit
+            op_code: JexOpCode::JumpForwardLong,
+            args: u16_args(304),
+        },
+        TestInstruction::new(JexOpCode::True),
+        TestInstruction {
+            // exit
+            op_code: JexOpCode::JumpForwardLong,
+            args: u16_args(1000),
+        },
+    ];
+    for _ in 0..300 {
+        instructions.push(TestInstruction::new(JexOpCode::False));
+    }
+    instructions.push(TestInstruction {
+        op_code: JexOpCode::JumpBackwardLong,
+        args: u16_args(307),
+    });
+    let result = run_chunk(TestChunk {
+        constants: vec![],
+        instructions,
+    });
+    assert!(result.unwrap().as_bool().unwrap());
+}
+
+#[test]
+fn it_should_get_and_set_local_with_wide_offset() {
+    let mut instructions = vec![];
+    for _ in 0..299 {
+        instructions.push(TestInstruction::new(JexOpCode::Null));
+    }
+    instructions.push(TestInstruction::new(JexOpCode::True));
+    instructions.push(TestInstruction {
+        // L299 = true
+        op_code: JexOpCode::SetLocalLong,
+        args: u16_args(299),
+    });
+    instructions.push(TestInstruction {
+        op_code: JexOpCode::GetLocalLong,
+        args: u16_args(299),
+    });
+    let result = run_chunk(TestChunk {
+        constants: vec![],
+        instructions,
+    });
+    assert!(result.unwrap().as_bool().unwrap());
+}
+
+#[test]
+fn it_should_get_field_and_global_with_wide_constant_index() {
+    let mut constants: Vec<JexConstant> = (0..256).map(JexConstant::Int).collect();
+    constants.push(JexConstant::from_str("name"));
+    let result = run_chunk(TestChunk {
+        constants,
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::True),
+            TestInstruction {
+                op_code: JexOpCode::SetFieldLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::DefineGlobalLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::GetGlobalLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::GetFieldLong,
+                args: u16_args(256),
+            },
+        ],
+    });
+    assert!(result.unwrap().as_bool().unwrap());
+}
+
+#[test]
+fn it_should_run_assembled_file_with_more_than_255_constants() {
+    let mut source = ".script\n NEW_INSTANCE\n CONSTANT_LONG 0\n".to_string();
+    for constant in 1..300 {
+        source.push_str(&format!(" CONSTANT_LONG {}\n ADD\n", constant));
+    }
+    // the name of the field is constant #300
+    source.push_str(" SET_FIELD_LONG total\n GET_FIELD_LONG total\n");
+    let bytes = assemble_to_bytes(&source).unwrap();
+    let bytecode = load_bytecode(&bytes).unwrap();
+    assert_eq!(301, bytecode.code.chunks[0].constants.len());
+    let mut machine = build_jex_machine(&bytecode.code);
+    assert!(machine.start());
+    assert_eq!(&JexValue::Int(44850), machine.peek_operand().unwrap());
+}
+
+#[test]
+fn it_should_check_and_delete_field_with_wide_constant_index() {
+    let mut constants: Vec<JexConstant> = (0..256).map(JexConstant::Int).collect();
+    constants.push(JexConstant::from_str("name"));
+    let result = run_chunk(TestChunk {
+        constants,
+        instructions: vec![
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction::new(JexOpCode::True),
+            TestInstruction {
+                op_code: JexOpCode::SetFieldLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::DeleteFieldLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::HasFieldLong,
+                args: u16_args(256),
+            },
+            TestInstruction::new(JexOpCode::NewInstance),
+            TestInstruction {
+                op_code: JexOpCode::GetFieldOrNullLong,
+                args: u16_args(256),
+            },
+            TestInstruction {
+                op_code: JexOpCode::NewArray,
+                args: vec![2],
+            },
+        ],
+    });
+    assert_eq!("[false, null]", result.unwrap().to_output_string());
+}
+
+#[test]
+fn it_should_define_and_invoke_methods_with_wide_constant_index() {
+    let mut source = ".script\n".to_string();
+    for constant in 0..256 {
+        source.push_str(&format!(" CONSTANT_LONG {}\n POP\n", constant));
+    }
+    source.push_str(
+        r#"
+    CLASS_LONG Point
+    CONSTANT_LONG @get
+    METHOD_LONG get
+    CALL 0
+    INVOKE_LONG get 0
+    IS_TYPE_LONG Int
+
+.function get 0
+    CONSTANT 5
+    RETURN
+"#,
+    );
+    let code = assemble(&source).unwrap();
+    assert!(code.chunks[0].constants.len() > 256);
+    let disassembled = disassemble(&code);
+    let line = |instruction: &str| {
+        disassembled
+            .lines()
+            .find(|line| line.contains(instruction))
+            .unwrap()
+            .to_string()
+    };
+    assert!(line("INVOKE_LONG 258 0").ends_with("; \"get\""));
+    assert!(line("IS_TYPE_LONG 259").ends_with("; \"Int\""));
+    assert_eq!(JexValue::Bool(true), run_source(&source).unwrap());
+}